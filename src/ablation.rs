@@ -0,0 +1,157 @@
+use super::*;
+
+/// The marginal cost of each plugin, measured by starting the editor once
+/// per plugin without it and comparing the total startup time to a run with
+/// every plugin.
+///
+/// Unlike the time attributed to a plugin in the log, this includes the cost
+/// of everything the plugin causes elsewhere, e.g autocommands, extra
+/// runtime files or filetype triggers.
+#[derive(Debug, Clone)]
+pub(crate) struct Ablation {
+  /// The run with every plugin loaded.
+  pub baseline: Report,
+  /// The total startup time of the baseline compared to the one without
+  /// each plugin, named after the plugin.
+  pub removals: Vec<Change>,
+  pub test: Significance,
+}
+
+impl Ablation {
+  /// The editor arguments that keep the scripts of a plugin from being
+  /// sourced and remove it from the `runtimepath`, which is done again
+  /// before every script is sourced since plugin managers add plugins while
+  /// the vimrc runs.
+  ///
+  /// The baseline is run with the same autocommands for a path that doesn't
+  /// exist, so their overhead doesn't count towards any plugin.
+  pub fn arguments(path: Option<&Path>) -> Vec<String> {
+    let path = path.map_or_else(
+      || String::from("/nonexistent/vim-profiler"),
+      |path| path.display().to_string(),
+    );
+
+    let escaped = escape(&path);
+
+    let pattern = if Path::new(&path).is_file() {
+      escaped.clone()
+    } else {
+      format!("{escaped}/*")
+    };
+
+    vec![
+      String::from("--cmd"),
+      format!("autocmd SourceCmd {pattern} :"),
+      String::from("--cmd"),
+      format!("autocmd SourcePre * set runtimepath-={escaped}"),
+    ]
+  }
+
+  /// Measure the marginal cost of every plugin of a baseline report that
+  /// has a path, where `run` starts the editor with extra arguments.
+  pub fn measure(
+    baseline: Report,
+    test: Significance,
+    run: impl Fn(Vec<String>) -> Result<Report>,
+  ) -> Result<Self> {
+    let total = baseline.total.clone().ok_or(Error::MissingTotal)?;
+
+    let mut removals = Vec::new();
+
+    for plugin in &baseline.plugins {
+      let Some(path) = &plugin.path else {
+        continue;
+      };
+
+      info!("Measuring startup without `{}` ...", plugin.name);
+
+      removals.push(Change::new(
+        plugin.name.clone(),
+        Some(total.clone()),
+        Some(
+          run(Self::arguments(Some(path)))?
+            .total
+            .ok_or(Error::MissingTotal)?,
+        ),
+        test,
+      ));
+    }
+
+    Ok(Self {
+      baseline,
+      removals,
+      test,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn arguments() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+
+    let plugin = tempdir.path().join("vim just");
+
+    fs::create_dir(&plugin)?;
+
+    let escaped = plugin.display().to_string().replace(' ', "\\ ");
+
+    assert_eq!(
+      Ablation::arguments(Some(&plugin)),
+      vec![
+        String::from("--cmd"),
+        format!("autocmd SourceCmd {escaped}/* :"),
+        String::from("--cmd"),
+        format!("autocmd SourcePre * set runtimepath-={escaped}"),
+      ]
+    );
+
+    let script = plugin.join("filetype.vim");
+
+    fs::write(&script, "")?;
+
+    assert_eq!(
+      Ablation::arguments(Some(&script))[1],
+      format!("autocmd SourceCmd {escaped}/filetype.vim :")
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn measure() -> Result<()> {
+    let baseline = Report::single(
+      50.0,
+      &[
+        ("ale", Some("/plugged/ale")),
+        ("vim-just", Some("/plugged/vim-just")),
+        ("filetype.vim", None),
+      ],
+    );
+
+    let ablation =
+      Ablation::measure(baseline, Significance::Welch, |arguments| {
+        Ok(Report::single(
+          if arguments[1].contains("/plugged/ale/") {
+            42.0
+          } else {
+            49.0
+          },
+          &[],
+        ))
+      })?;
+
+    let removals = ablation
+      .removals
+      .iter()
+      .map(|removal| (removal.name.as_str(), removal.delta(Estimator::Mean)))
+      .collect::<HashMap<_, _>>();
+
+    assert_eq!(removals, HashMap::from([("ale", -8.0), ("vim-just", -1.0)]));
+
+    Ok(())
+  }
+}
@@ -3,39 +3,226 @@ use super::*;
 #[derive(Debug, Parser)]
 #[command(name = "vim-profiler", about = "A vim profiling tool.")]
 pub(crate) struct Arguments {
-  #[arg(short, long, default_value = "vim")]
-  /// The command to run, e.g vim or neovim.
-  command: Command,
-  #[arg(short = 'n', long)]
+  #[arg(last = true, global = true, value_name = "ARGS")]
+  /// Extra arguments passed to the editor, e.g `-- -S session.vim`.
+  arguments: Vec<String>,
+  #[arg(long, global = true)]
+  /// Results to check regressions against, either a JSON or CSV export.
+  baseline: Option<PathBuf>,
+  #[arg(long = "budget-file", global = true)]
+  /// A budget file with time ceilings for plugins and phases, by default
+  /// `vp-budget.toml` next to the vimrc.
+  budget_file: Option<PathBuf>,
+  #[arg(long = "budget-plugin", global = true)]
+  /// Fail when any plugin takes longer than this many milliseconds.
+  budget_plugin: Option<f64>,
+  #[arg(long = "budget-total", global = true)]
+  /// Fail when the total startup takes longer than this many milliseconds.
+  budget_total: Option<f64>,
+  #[arg(short, long, default_value = "vim", global = true)]
+  /// The editor to run, e.g vim, nvim, `gvim -v` or the path of an
+  /// executable, which is detected as Vim or Neovim from its `--version`.
+  command: String,
+  #[arg(short = 'u', long, global = true)]
+  /// A vimrc (init.vim, init.lua) to start the editor with instead of the
+  /// default one.
+  config: Option<PathBuf>,
+  #[arg(short = 'n', long, global = true)]
   /// The number of plugins to list in the output.
   count: Option<usize>,
-  #[arg(short, long)]
+  #[arg(long, default_value = "mean", global = true)]
+  /// How plugins are ranked, either mean, median, trimmed or winsorized.
+  estimator: Estimator,
+  #[arg(short, long, global = true)]
   /// Export the results to a CSV file.
   export: Option<PathBuf>,
-  #[arg(short, long)]
+  #[arg(short, long, global = true)]
   /// A file to open
   file: Option<PathBuf>,
-  #[arg(short, long)]
+  #[arg(long, global = true)]
+  /// Export the sourcing tree as folded stacks, e.g for flamegraph tools.
+  folded: Option<PathBuf>,
+  #[arg(long = "history-file", global = true)]
+  /// The file runs are recorded to, by default
+  /// `$XDG_DATA_HOME/vim-profiler/history.jsonl`.
+  history_file: Option<PathBuf>,
+  #[arg(long, default_value = "t", global = true)]
+  /// How the confidence interval of the mean is computed, either t or
+  /// bootstrap.
+  interval: Interval,
+  #[arg(short, long, global = true)]
   /// The number of iterations.
   iter: Option<i64>,
-  #[arg(short, long)]
+  #[arg(long, global = true)]
+  /// Export the results to a JSON file, or standard output with `-`.
+  json: Option<PathBuf>,
+  #[arg(long = "max-regression", requires = "baseline", global = true)]
+  /// Fail when the total startup or any plugin is slower than the baseline
+  /// by more than this percentage.
+  max_regression: Option<f64>,
+  #[arg(long, global = true)]
+  /// Export the results to a NDJSON file, or standard output with `-`.
+  ndjson: Option<PathBuf>,
+  #[arg(long, default_value = "none", global = true)]
+  /// Discard outlying times before computing statistics, either none, iqr or
+  /// mad.
+  outliers: Outliers,
+  #[arg(long, global = true)]
+  /// Show how long each startup phase took.
+  phases: bool,
+  #[arg(short, long, global = true)]
   /// Plot the data and save it to a SVG file
   plot: Option<PathBuf>,
-  #[arg(short = 'x', long)]
+  #[arg(long = "plugin-dir", global = true)]
+  /// A directory holding plugins, on top of the ones found in the log.
+  plugin_dir: Vec<PathBuf>,
+  #[arg(short = 'x', long, global = true)]
   /// Precision in the output.
   precision: Option<usize>,
-  #[arg(short, long)]
+  #[arg(long, global = true)]
+  /// Record the run to the history, see `vp history`.
+  record: bool,
+  #[arg(short, long, global = true)]
   /// Display the plugin times in reverse order (fastest first).
   reverse: bool,
-  #[arg(short, long)]
+  #[command(subcommand)]
+  subcommand: Option<Subcommand>,
+  #[arg(short, long, global = true)]
   /// Show system plugins in the output.
   sys: bool,
-  #[arg(short, long)]
+  #[arg(long = "sys-dir", global = true)]
+  /// A directory holding system runtime files, on top of `$VIMRUNTIME`.
+  sys_dir: Vec<PathBuf>,
+  #[arg(long, default_value = "80x24", global = true)]
+  /// The size of the pseudo-terminal Vim is started in, e.g 120x40.
+  terminal: Terminal,
+  #[arg(
+    long,
+    default_value = "30",
+    value_parser = clap::value_parser!(u64).range(1..),
+    global = true
+  )]
+  /// The number of seconds a single start of the editor may take before it's
+  /// killed, e.g when it waits at a prompt.
+  timeout: u64,
+  #[arg(short, long, default_value = "self", global = true)]
+  /// The column to time plugins with, either self or self+sourced.
+  timing: TimingMode,
+  #[arg(long, global = true)]
+  /// Export a Chrome trace of the startup timeline, e.g for Perfetto.
+  trace: Option<PathBuf>,
+  #[arg(short, long, global = true)]
   /// Add informative messages during program execution.
   verbose: bool,
+  #[arg(
+    short,
+    long,
+    value_parser = clap::value_parser!(i64).range(0..),
+    global = true
+  )]
+  /// The number of iterations to run before measuring, e.g to warm up caches.
+  warmup: Option<i64>,
 }
 
 impl Arguments {
+  /// Measure the marginal cost of each plugin by removing it.
+  fn ablate(&self, editor: &Editor, test: Significance) -> Result<()> {
+    info!("Measuring startup with every plugin ...");
+
+    let baseline = self
+      .worker(editor, None, Ablation::arguments(None), Vec::new())
+      .run()?;
+
+    let ablation = Ablation::measure(baseline, test, |arguments| {
+      self.worker(editor, None, arguments, Vec::new()).run()
+    })?;
+
+    self.printer().ablation(&ablation);
+
+    Ok(())
+  }
+
+  /// The limits the run is checked against, loading the baseline if there
+  /// is one.
+  fn budget(&self, editor: &Editor) -> Result<Budget> {
+    Ok(Budget {
+      baseline: self
+        .baseline
+        .as_deref()
+        .map(|path| self.snapshot(Some(editor), path))
+        .transpose()?,
+      max_regression: self.max_regression,
+      plugin: self.budget_plugin,
+      total: self.budget_total,
+    })
+  }
+
+  /// Compare the snapshots taken before and after a change. The editor is
+  /// only detected when one of them is taken by starting it.
+  fn compare(
+    &self,
+    before: &Path,
+    after: &Path,
+    test: Significance,
+  ) -> Result<()> {
+    let editor = [before, after]
+      .iter()
+      .any(|path| !Snapshot::exported(path))
+      .then(|| Editor::detect(&self.command))
+      .transpose()?;
+
+    let comparison = Comparison::new(
+      &self.snapshot(editor.as_ref(), before)?,
+      &self.snapshot(editor.as_ref(), after)?,
+      test,
+    );
+
+    self.printer().compare(before, after, &comparison);
+
+    Ok(())
+  }
+
+  fn history(&self) -> Result<History> {
+    Ok(History {
+      path: self
+        .history_file
+        .clone()
+        .or_else(History::default_path)
+        .ok_or(Error::HistoryPath)?,
+    })
+  }
+
+  /// Measure the standalone cost of each plugin of the config.
+  fn isolate(
+    &self,
+    editor: &Editor,
+    depends: &[Dependency],
+    test: Significance,
+  ) -> Result<()> {
+    info!("Measuring startup with the whole config ...");
+
+    let config = self.worker(editor, None, Vec::new(), Vec::new()).run()?;
+
+    let isolation =
+      Isolation::measure(config, depends, test, |arguments, directories| {
+        self.worker(editor, None, arguments, directories).run()
+      })?;
+
+    self.printer().isolation(&isolation);
+
+    Ok(())
+  }
+
+  fn printer(&self) -> Printer {
+    Printer::new(
+      self.reverse,
+      self.count,
+      self.precision,
+      self.estimator,
+      self.interval,
+    )
+  }
+
   pub fn run(self) -> Result<()> {
     if self.verbose {
       // SAFETY: This is called at program startup before any other threads are spawned.
@@ -45,26 +232,208 @@ impl Arguments {
     env_logger::init();
     info!("Starting run ...");
 
-    let plugins =
-      Worker::new(self.command, self.iter.unwrap_or(1), self.sys, self.file)
-        .run()?
-        .sort(self.reverse);
+    if let Some(path) = &self.config {
+      if !path.is_file() {
+        return Err(Error::ConfigNotFound { path: path.clone() });
+      }
+    }
+
+    if let Some(Subcommand::Compare {
+      after,
+      before,
+      test,
+    }) = &self.subcommand
+    {
+      return self.compare(before, after, *test);
+    }
+
+    if let Some(Subcommand::History { last }) = &self.subcommand {
+      return self.trend(*last);
+    }
+
+    // The remaining subcommands all start the editor.
+    let editor = Editor::detect(&self.command)?;
+
+    if let Some(Subcommand::Ablate { test }) = &self.subcommand {
+      return self.ablate(&editor, *test);
+    }
+
+    if let Some(Subcommand::Isolate { depends, test }) = &self.subcommand {
+      return self.isolate(&editor, depends, *test);
+    }
+
+    let mut report =
+      self.worker(&editor, None, Vec::new(), Vec::new()).run()?;
+
+    if let Some(path) = self
+      .budget_file
+      .clone()
+      .or_else(|| BudgetFile::discover(&editor.command, self.config.as_deref()))
+    {
+      info!("Reading budgets from `{}` ...", path.display());
+      BudgetFile::load(&path)?.apply(&mut report)?;
+    }
+
+    if self.record {
+      self.history()?.append(&report)?;
+    }
+
+    report.plugins.sort(self.reverse, self.estimator);
+
+    if let Some(Subcommand::Explain { plugin }) = &self.subcommand {
+      let files = report.files.get(plugin).ok_or(Error::UnknownPlugin {
+        plugin: plugin.clone(),
+      })?;
+
+      self.printer().explain(plugin, files);
 
-    if self.export.is_none() && self.plot.is_none() {
-      Printer::new(self.reverse, self.count, self.precision).summary(&plugins);
       return Ok(());
     }
 
+    let violations = self
+      .budget(&editor)?
+      .check(&Snapshot::from(&report), self.estimator);
+
+    if self.export.is_none()
+      && self.folded.is_none()
+      && self.json.is_none()
+      && self.ndjson.is_none()
+      && self.plot.is_none()
+      && self.trace.is_none()
+    {
+      let printer = self.printer();
+
+      printer.summary(
+        &report.run.version,
+        &report.plugins,
+        report.total.as_ref(),
+        report.warmup.as_ref(),
+      );
+
+      if self.phases {
+        printer.phases(&report.phases);
+      }
+    }
+
     if let Some(path) = self.export {
       info!("Writing statistics to CSV file ...");
-      write(path, &plugins)?;
+      write(path, &report, self.phases)?;
+    }
+
+    if let Some(path) = self.folded {
+      info!("Writing folded stacks ...");
+      folded(path, &report)?;
+    }
+
+    if let Some(path) = self.json {
+      info!("Writing JSON ...");
+      json(path, &report)?;
+    }
+
+    if let Some(path) = self.ndjson {
+      info!("Writing NDJSON ...");
+      ndjson(path, &report)?;
     }
 
     if let Some(path) = self.plot {
       info!("Plotting statistics ...");
-      plot(path, &plugins)?;
+      plot(path, &report)?;
+    }
+
+    if let Some(path) = self.trace {
+      info!("Writing trace ...");
+      trace(path, &report)?;
+    }
+
+    if !violations.is_empty() {
+      return Err(Error::BudgetExceeded { violations });
     }
 
     Ok(())
   }
+
+  /// Load a snapshot from a JSON or CSV export, or take one by starting the
+  /// editor with any other file as its vimrc, detecting the editor if it
+  /// wasn't given.
+  fn snapshot(&self, editor: Option<&Editor>, path: &Path) -> Result<Snapshot> {
+    match path.extension().and_then(|extension| extension.to_str()) {
+      Some("csv") => Snapshot::from_csv(path),
+      Some("json") => Snapshot::from_json(path),
+      _ => {
+        if !path.is_file() {
+          return Err(Error::ConfigNotFound {
+            path: path.to_owned(),
+          });
+        }
+
+        let editor = match editor {
+          Some(editor) => editor.clone(),
+          None => Editor::detect(&self.command)?,
+        };
+
+        info!("Measuring startup with `{}` ...", path.display());
+
+        Ok(Snapshot::from(
+          &self
+            .worker(&editor, Some(path.to_owned()), Vec::new(), Vec::new())
+            .run()?,
+        ))
+      }
+    }
+  }
+
+  /// Show the trends of the recorded runs, optionally only the most recent
+  /// ones.
+  fn trend(&self, last: Option<usize>) -> Result<()> {
+    let mut recordings = self.history()?.load()?;
+
+    if let Some(last) = last {
+      recordings.drain(..recordings.len().saturating_sub(last));
+    }
+
+    self.printer().history(&recordings);
+
+    if let Some(path) = &self.plot {
+      info!("Plotting trends ...");
+      trend(path.clone(), &recordings, self.estimator, self.count)?;
+    }
+
+    Ok(())
+  }
+
+  /// A worker for the options of the run, with a vimrc overriding `--config`,
+  /// editor arguments before the ones given after `--` and plugin
+  /// directories on top of `--plugin-dir`.
+  fn worker(
+    &self,
+    editor: &Editor,
+    config: Option<PathBuf>,
+    arguments: Vec<String>,
+    plugin_directories: Vec<PathBuf>,
+  ) -> Worker {
+    Worker {
+      arguments: arguments
+        .into_iter()
+        .chain(self.arguments.iter().cloned())
+        .collect(),
+      config: config.or_else(|| self.config.clone()),
+      editor: editor.clone(),
+      estimator: self.estimator,
+      file: self.file.clone(),
+      iter: self.iter.unwrap_or(1),
+      outliers: self.outliers,
+      plugin_directories: self
+        .plugin_dir
+        .iter()
+        .cloned()
+        .chain(plugin_directories)
+        .collect(),
+      sys: self.sys,
+      system_directories: self.sys_dir.clone(),
+      terminal: self.terminal,
+      timeout: Duration::from_secs(self.timeout),
+      timing: self.timing,
+      warmup: self.warmup.unwrap_or(0),
+    }
+  }
 }
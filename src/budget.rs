@@ -0,0 +1,166 @@
+use super::*;
+
+/// The limits a run is checked against, e.g to fail a CI pipeline when a
+/// heavy plugin is added.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Budget {
+  /// The snapshot regressions are measured against.
+  pub baseline: Option<Snapshot>,
+  /// The largest allowed regression versus the baseline, in percent.
+  pub max_regression: Option<f64>,
+  /// The longest any single plugin may take, in milliseconds.
+  pub plugin: Option<f64>,
+  /// The longest the whole startup may take, in milliseconds.
+  pub total: Option<f64>,
+}
+
+impl Budget {
+  /// Every limit a snapshot goes over, where times are estimated with
+  /// `estimator`.
+  ///
+  /// The budget a plugin was given by a budget file takes precedence over
+  /// the per-plugin budget.
+  pub fn check(
+    &self,
+    snapshot: &Snapshot,
+    estimator: Estimator,
+  ) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    if let Some((budget, total)) = self.total.zip(snapshot.total.as_ref()) {
+      let time = estimator.estimate(total);
+
+      if time > budget {
+        violations.push(Violation::Total { budget, time });
+      }
+    }
+
+    for plugin in &snapshot.plugins {
+      if let Some(budget) = plugin.budget.or(self.plugin) {
+        let time = estimator.estimate(plugin);
+
+        if time > budget {
+          violations.push(Violation::Plugin {
+            budget,
+            name: plugin.name.clone(),
+            time,
+          });
+        }
+      }
+    }
+
+    for phase in &snapshot.phases {
+      if let Some(budget) = phase.budget {
+        let time = estimator.estimate(phase);
+
+        if time > budget {
+          violations.push(Violation::Phase {
+            budget,
+            name: phase.name.clone(),
+            time,
+          });
+        }
+      }
+    }
+
+    if let Some((maximum, baseline)) =
+      self.max_regression.zip(self.baseline.as_ref())
+    {
+      let pairs = baseline.total.iter().zip(&snapshot.total).chain(
+        baseline.plugins.iter().filter_map(|before| {
+          snapshot
+            .plugins
+            .iter()
+            .find(|after| after.name == before.name)
+            .map(|after| (before, after))
+        }),
+      );
+
+      for (before, after) in pairs {
+        let (baseline, time) =
+          (estimator.estimate(before), estimator.estimate(after));
+
+        if baseline > 0.0 && time > baseline * (1.0 + maximum / 100.0) {
+          violations.push(Violation::Regression {
+            baseline,
+            maximum,
+            name: after.name.clone(),
+            time,
+          });
+        }
+      }
+    }
+
+    violations
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn check() {
+    let snapshot = |total: f64, plugins: Vec<(&str, f64)>| Snapshot {
+      phases: Vec::new(),
+      plugins: plugins
+        .into_iter()
+        .map(|(name, time)| Plugin::new(name.into(), vec![time]))
+        .collect(),
+      total: Some(Plugin::new("total".into(), vec![total])),
+    };
+
+    let budget = Budget {
+      baseline: Some(snapshot(50.0, vec![("ale", 4.0), ("vim-just", 1.0)])),
+      max_regression: Some(10.0),
+      plugin: Some(5.0),
+      total: Some(60.0),
+    };
+
+    assert_eq!(
+      budget.check(
+        &snapshot(54.0, vec![("ale", 4.2), ("vim-just", 1.05)]),
+        Estimator::Mean
+      ),
+      Vec::new()
+    );
+
+    assert_eq!(
+      budget.check(
+        &snapshot(
+          65.0,
+          vec![("ale", 4.5), ("vim-just", 1.0), ("coc.nvim", 8.0)]
+        ),
+        Estimator::Mean
+      ),
+      vec![
+        Violation::Total {
+          budget: 60.0,
+          time: 65.0
+        },
+        Violation::Plugin {
+          budget: 5.0,
+          name: "coc.nvim".into(),
+          time: 8.0
+        },
+        Violation::Regression {
+          baseline: 50.0,
+          maximum: 10.0,
+          name: "total".into(),
+          time: 65.0
+        },
+        Violation::Regression {
+          baseline: 4.0,
+          maximum: 10.0,
+          name: "ale".into(),
+          time: 4.5
+        },
+      ]
+    );
+
+    assert_eq!(
+      Budget::default().check(&snapshot(1000.0, vec![]), Estimator::Mean),
+      Vec::new()
+    );
+  }
+}
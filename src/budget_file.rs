@@ -0,0 +1,197 @@
+use super::*;
+
+/// Time ceilings for plugins and startup phases, usually kept in a
+/// `vp-budget.toml` file next to the vimrc, e.g:
+///
+/// tolerance = 10
+///
+/// [plugins]
+/// "coc.nvim" = 15
+/// "vim-*" = { ceiling = 2, tolerance = 50 }
+///
+/// [phases]
+/// "loading plugins" = 40
+///
+/// Plugins and phases are matched by name, or by glob pattern. An exact name
+/// wins over patterns, and the longest matching pattern wins over shorter
+/// ones.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct BudgetFile {
+  pub phases: BTreeMap<String, Ceiling>,
+  pub plugins: BTreeMap<String, Ceiling>,
+  /// The percentage every ceiling may be exceeded by, unless a ceiling has
+  /// its own.
+  pub tolerance: f64,
+}
+
+impl BudgetFile {
+  /// The name of the budget file looked up next to the vimrc.
+  const NAME: &'static str = "vp-budget.toml";
+
+  /// Set the budget of every plugin and phase of a report that has a
+  /// ceiling.
+  pub fn apply(&self, report: &mut Report) -> Result<()> {
+    for plugin in &mut report.plugins {
+      plugin.budget = self.limit(&self.plugins, &plugin.name)?;
+    }
+
+    for phase in &mut report.phases {
+      phase.budget = self.limit(&self.phases, &phase.name)?;
+    }
+
+    Ok(())
+  }
+
+  /// Find the budget file next to the vimrc of an editor, i.e next to the
+  /// given vimrc, if any, or in `~/.vim` or `~` for Vim and in
+  /// `$XDG_CONFIG_HOME/nvim` for Neovim.
+  pub fn discover(command: &Command, config: Option<&Path>) -> Option<PathBuf> {
+    if let Some(config) = config {
+      return config
+        .parent()
+        .map(|directory| directory.join(Self::NAME))
+        .filter(|path| path.is_file());
+    }
+
+    let home = PathBuf::from(env::var_os("HOME")?);
+
+    let directories = match command {
+      Command::Neovim => vec![env::var_os("XDG_CONFIG_HOME")
+        .map_or_else(|| home.join(".config"), PathBuf::from)
+        .join("nvim")],
+      Command::Vim => vec![home.join(".vim"), home],
+    };
+
+    directories
+      .into_iter()
+      .map(|directory| directory.join(Self::NAME))
+      .find(|path| path.is_file())
+  }
+
+  /// The limit of a plugin or phase, tolerance included, if any of the
+  /// ceilings match its name.
+  fn limit(
+    &self,
+    ceilings: &BTreeMap<String, Ceiling>,
+    name: &str,
+  ) -> Result<Option<f64>> {
+    if let Some(ceiling) = ceilings.get(name) {
+      return Ok(Some(ceiling.limit(self.tolerance)));
+    }
+
+    let mut best: Option<(&str, &Ceiling)> = None;
+
+    for (pattern, ceiling) in ceilings {
+      let matches = glob::Pattern::new(pattern)
+        .context(error::BudgetPatternSnafu {
+          pattern: pattern.clone(),
+        })?
+        .matches(name);
+
+      if matches && best.is_none_or(|(best, _)| pattern.len() > best.len()) {
+        best = Some((pattern, ceiling));
+      }
+    }
+
+    Ok(best.map(|(_, ceiling)| ceiling.limit(self.tolerance)))
+  }
+
+  pub fn load(path: &Path) -> Result<Self> {
+    toml::from_str(&fs::read_to_string(path)?)
+      .context(error::BudgetFileSnafu { path })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn apply() -> Result<()> {
+    let file = toml::from_str::<BudgetFile>(&dedent(
+      r#"
+      tolerance = 10
+
+      [plugins]
+      "coc.nvim" = 15
+      "vim-*" = { ceiling = 2, tolerance = 50 }
+      "vim-just*" = 4
+      "*" = 10.5
+
+      [phases]
+      "loading plugins" = 40
+      "#,
+    ))
+    .unwrap();
+
+    let mut report = Report::new(
+      &[Sample {
+        phases: HashMap::from([
+          (Phase::LoadingPlugins, 30.0),
+          (Phase::ArgumentParsing, 1.0),
+        ]),
+        plugins: HashMap::from([
+          ("coc.nvim".into(), 16.0),
+          ("vim-rooter".into(), 3.5),
+          ("vim-justfile".into(), 1.0),
+          ("ale".into(), 12.0),
+        ]),
+        ..Sample::default()
+      }],
+      &[],
+      Run::default(),
+    );
+
+    file.apply(&mut report)?;
+
+    let budgets = report
+      .plugins
+      .iter()
+      .chain(&report.phases)
+      .map(|plugin| {
+        (
+          plugin.name.as_str(),
+          (
+            plugin
+              .budget
+              .map(|budget| (budget * 1000.0).round() / 1000.0),
+            plugin.over_budget(Estimator::Mean),
+          ),
+        )
+      })
+      .collect::<HashMap<_, _>>();
+
+    assert_eq!(
+      budgets,
+      HashMap::from([
+        ("coc.nvim", (Some(16.5), false)),
+        ("vim-rooter", (Some(3.0), true)),
+        ("vim-justfile", (Some(4.4), false)),
+        ("ale", (Some(11.55), true)),
+        ("loading plugins", (Some(44.0), false)),
+        ("argument parsing", (None, false)),
+      ])
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn discover_next_to_config() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+
+    let config = tempdir.path().join("candidate.vim");
+
+    assert_eq!(BudgetFile::discover(&Command::Vim, Some(&config)), None);
+
+    fs::write(tempdir.path().join(BudgetFile::NAME), "tolerance = 5")?;
+
+    assert_eq!(
+      BudgetFile::discover(&Command::Neovim, Some(&config)),
+      Some(tempdir.path().join(BudgetFile::NAME))
+    );
+
+    Ok(())
+  }
+}
@@ -0,0 +1,31 @@
+use super::*;
+
+/// The expected time of a plugin or phase in a budget file, either a plain
+/// number of milliseconds or a table with its own tolerance, e.g
+/// `{ ceiling = 5, tolerance = 20 }`.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum Ceiling {
+  Detailed {
+    ceiling: f64,
+    tolerance: Option<f64>,
+  },
+  Time(f64),
+}
+
+impl Ceiling {
+  /// The longest time allowed, with the tolerance in percent added on top
+  /// of the ceiling. The file wide `tolerance` is used unless the ceiling
+  /// has its own.
+  pub fn limit(self, tolerance: f64) -> f64 {
+    let (ceiling, tolerance) = match self {
+      Ceiling::Detailed {
+        ceiling,
+        tolerance: own,
+      } => (ceiling, own.unwrap_or(tolerance)),
+      Ceiling::Time(ceiling) => (ceiling, tolerance),
+    };
+
+    ceiling * (1.0 + tolerance / 100.0)
+  }
+}
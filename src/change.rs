@@ -0,0 +1,47 @@
+use super::*;
+
+/// A plugin measured before and after a change, either of which is missing
+/// if the plugin was added or removed.
+#[derive(Debug, Clone)]
+pub(crate) struct Change {
+  pub after: Option<Plugin>,
+  pub before: Option<Plugin>,
+  pub name: String,
+  /// The two-sided p-value of the difference, if both sides have enough
+  /// times to be tested.
+  pub p_value: Option<f64>,
+}
+
+impl Change {
+  /// How much slower the plugin got according to an estimator, where added
+  /// and removed plugins count as taking no time on the missing side.
+  pub fn delta(&self, estimator: Estimator) -> f64 {
+    let estimate = |plugin: &Option<Plugin>| {
+      plugin
+        .as_ref()
+        .map(|plugin| estimator.estimate(plugin))
+        .unwrap_or_default()
+    };
+
+    estimate(&self.after) - estimate(&self.before)
+  }
+
+  pub fn new(
+    name: String,
+    before: Option<Plugin>,
+    after: Option<Plugin>,
+    test: Significance,
+  ) -> Self {
+    let p_value = before
+      .as_ref()
+      .zip(after.as_ref())
+      .and_then(|(before, after)| test.p_value(before, after));
+
+    Self {
+      after,
+      before,
+      name,
+      p_value,
+    }
+  }
+}
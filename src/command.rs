@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum Command {
   Neovim,
   Vim,
@@ -14,17 +14,3 @@ impl Display for Command {
     }
   }
 }
-
-impl FromStr for Command {
-  type Err = Error;
-
-  fn from_str(value: &str) -> Result<Self> {
-    match value {
-      "vim" => Ok(Command::Vim),
-      "nvim" | "neovim" => Ok(Command::Neovim),
-      _ => Err(Error::InvalidCommand {
-        cmd: value.to_owned(),
-      }),
-    }
-  }
-}
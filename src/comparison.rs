@@ -0,0 +1,150 @@
+use super::*;
+
+/// The changes in plugin and total startup times between two snapshots.
+#[derive(Debug, Clone)]
+pub(crate) struct Comparison {
+  pub plugins: Vec<Change>,
+  pub test: Significance,
+  pub total: Option<Change>,
+}
+
+impl Comparison {
+  /// Pair up the plugins of two snapshots by name, in the order of the
+  /// snapshot taken before, followed by the plugins that were added.
+  pub fn new(before: &Snapshot, after: &Snapshot, test: Significance) -> Self {
+    let find = |snapshot: &Snapshot, name: &str| {
+      snapshot
+        .plugins
+        .iter()
+        .find(|plugin| plugin.name == name)
+        .cloned()
+    };
+
+    let plugins = before
+      .plugins
+      .iter()
+      .map(|plugin| {
+        Change::new(
+          plugin.name.clone(),
+          Some(plugin.clone()),
+          find(after, &plugin.name),
+          test,
+        )
+      })
+      .chain(
+        after
+          .plugins
+          .iter()
+          .filter(|plugin| find(before, &plugin.name).is_none())
+          .map(|plugin| {
+            Change::new(plugin.name.clone(), None, Some(plugin.clone()), test)
+          }),
+      )
+      .collect();
+
+    let total = (before.total.is_some() || after.total.is_some()).then(|| {
+      Change::new(
+        String::from("total"),
+        before.total.clone(),
+        after.total.clone(),
+        test,
+      )
+    });
+
+    Self {
+      plugins,
+      test,
+      total,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn new() {
+    let plugin = |name: &str, times: Vec<f64>| Plugin::new(name.into(), times);
+
+    let before = Snapshot {
+      phases: Vec::new(),
+      plugins: vec![
+        plugin("ale", vec![2.0, 2.1, 1.9, 2.0]),
+        plugin("vim-just", vec![1.0, 1.1, 0.9, 1.0]),
+        plugin("coc.nvim", vec![8.0, 8.2, 7.9, 8.1]),
+      ],
+      total: Some(plugin("total", vec![20.0, 21.0, 19.0, 20.0])),
+    };
+
+    let after = Snapshot {
+      phases: Vec::new(),
+      plugins: vec![
+        plugin("vim-just", vec![1.0, 0.9, 1.1, 1.0]),
+        plugin("ale", vec![4.0, 4.1, 3.9, 4.0]),
+        plugin("vim-rooter", vec![0.5, 0.5]),
+      ],
+      total: Some(plugin("total", vec![16.0, 17.0, 15.0, 16.0])),
+    };
+
+    let comparison = Comparison::new(&before, &after, Significance::Welch);
+
+    let names = comparison
+      .plugins
+      .iter()
+      .map(|change| change.name.as_str())
+      .collect::<Vec<_>>();
+
+    assert_eq!(names, vec!["ale", "vim-just", "coc.nvim", "vim-rooter"]);
+
+    let (ale, just, coc, rooter) = (
+      &comparison.plugins[0],
+      &comparison.plugins[1],
+      &comparison.plugins[2],
+      &comparison.plugins[3],
+    );
+
+    assert!(approx_eq!(
+      f64,
+      ale.delta(Estimator::Mean),
+      2.0,
+      epsilon = 1e-9
+    ));
+    assert!(ale.p_value.unwrap() < 0.001);
+
+    assert!(approx_eq!(
+      f64,
+      just.delta(Estimator::Mean),
+      0.0,
+      epsilon = 1e-9
+    ));
+    assert!(just.p_value.unwrap() > 0.5);
+
+    assert!(coc.after.is_none());
+    assert!(coc.p_value.is_none());
+    assert!(approx_eq!(
+      f64,
+      coc.delta(Estimator::Mean),
+      -8.05,
+      epsilon = 1e-9
+    ));
+
+    assert!(rooter.before.is_none());
+    assert!(approx_eq!(
+      f64,
+      rooter.delta(Estimator::Mean),
+      0.5,
+      ulps = 2
+    ));
+
+    let total = comparison.total.unwrap();
+
+    assert!(approx_eq!(
+      f64,
+      total.delta(Estimator::Median),
+      -4.0,
+      ulps = 2
+    ));
+    assert!(total.p_value.unwrap() < 0.05);
+  }
+}
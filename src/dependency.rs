@@ -0,0 +1,53 @@
+use super::*;
+
+/// The plugins a plugin needs to be loaded on its own, given as
+/// `plugin=dependency,...`, e.g `telescope.nvim=plenary.nvim`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Dependency {
+  pub dependencies: Vec<String>,
+  pub plugin: String,
+}
+
+impl FromStr for Dependency {
+  type Err = Error;
+
+  fn from_str(value: &str) -> Result<Self> {
+    match value.split_once('=') {
+      Some((plugin, dependencies)) if !plugin.is_empty() => Ok(Self {
+        dependencies: dependencies
+          .split(',')
+          .filter(|dependency| !dependency.is_empty())
+          .map(str::to_owned)
+          .collect(),
+        plugin: plugin.to_owned(),
+      }),
+      _ => Err(Error::InvalidDependency {
+        value: value.to_owned(),
+      }),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn from_str() {
+    assert_eq!(
+      "telescope.nvim=plenary.nvim,nvim-web-devicons"
+        .parse::<Dependency>()
+        .ok(),
+      Some(Dependency {
+        dependencies: vec![
+          String::from("plenary.nvim"),
+          String::from("nvim-web-devicons")
+        ],
+        plugin: String::from("telescope.nvim"),
+      })
+    );
+
+    assert!("telescope.nvim".parse::<Dependency>().is_err());
+    assert!("=plenary.nvim".parse::<Dependency>().is_err());
+  }
+}
@@ -0,0 +1,404 @@
+use super::*;
+
+/// The editor executable to profile, along with its flavor as detected from
+/// its `--version` output.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Editor {
+  /// Arguments always passed to the executable, e.g `-v` for `gvim -v`.
+  pub arguments: Vec<String>,
+  pub command: Command,
+  pub program: String,
+  /// The first line of the `--version` output, e.g `NVIM v0.10.0`.
+  pub version: String,
+}
+
+impl Editor {
+  /// How long the output of an editor is still read for once it exits or
+  /// is killed after the timeout ran out.
+  const GRACE: Duration = Duration::from_millis(100);
+
+  /// A process running the executable with its arguments.
+  pub fn command(&self) -> Cmd {
+    let mut command = Cmd::new(&self.program);
+    command.args(&self.arguments);
+    command
+  }
+
+  /// Detect the flavor and version of an editor given as an executable,
+  /// either a name on the `PATH` or a path, followed by any arguments split
+  /// on whitespace, e.g `~/neovim/build/bin/nvim` or `gvim -v`. A path to an
+  /// existing file is used as is, even when it contains whitespace.
+  ///
+  /// `neovim` is accepted as an alias for `nvim`.
+  pub fn detect(value: &str) -> Result<Self> {
+    let mut words = if Path::new(value).is_file() {
+      vec![value.to_owned()]
+    } else {
+      value.split_whitespace().map(str::to_owned).collect()
+    }
+    .into_iter();
+
+    let program = match words.next().as_deref() {
+      Some("neovim") => String::from("nvim"),
+      Some(program) => program.to_owned(),
+      None => {
+        return Err(Error::InvalidCommand {
+          cmd: value.to_owned(),
+        })
+      }
+    };
+
+    let editor = Self {
+      arguments: words.collect(),
+      command: Command::Vim,
+      program,
+      version: String::new(),
+    };
+
+    let output = editor
+      .command()
+      .arg("--version")
+      .stdin(Stdio::null())
+      .stderr(Stdio::null())
+      .output()
+      .context(error::EditorVersionSnafu {
+        program: editor.program.clone(),
+      })?;
+
+    let version = String::from_utf8_lossy(&output.stdout)
+      .lines()
+      .next()
+      .unwrap_or_default()
+      .trim()
+      .to_owned();
+
+    let command = if version.starts_with("NVIM") {
+      Command::Neovim
+    } else if version.starts_with("VIM") {
+      Command::Vim
+    } else {
+      return Err(Error::UnknownEditor {
+        program: editor.program,
+        version,
+      });
+    };
+
+    let editor = Self {
+      command,
+      version,
+      ..editor
+    };
+
+    info!("Detected `{}`: {}", editor, editor.version);
+
+    Ok(editor)
+  }
+
+  /// Kill a started editor, along with its process group if it leads one.
+  fn kill(child: &mut Child) -> Result<()> {
+    #[cfg(unix)]
+    if let Ok(pid) = i32::try_from(child.id()) {
+      if nix::sys::signal::killpg(
+        nix::unistd::Pid::from_raw(pid),
+        nix::sys::signal::Signal::SIGKILL,
+      )
+      .is_ok()
+      {
+        return Ok(());
+      }
+    }
+
+    Ok(child.kill()?)
+  }
+
+  /// The messages an editor wrote to its terminal or standard error, without
+  /// terminal escape sequences or blank lines, e.g
+  /// `E492: Not an editor command: foo`.
+  pub fn messages(output: &[u8]) -> Result<String> {
+    let escapes = RegexBuilder::new(
+      r"\x1b(?:\[[0-9;?>=]*[ -/]*[@-~]|\][^\x07]*\x07|[()][0-9A-Za-z]|[=>78])|\r",
+    )
+    .build()?;
+
+    Ok(
+      escapes
+        .replace_all(&String::from_utf8_lossy(output), "\n")
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && *line != "~")
+        .collect::<Vec<_>>()
+        .join("\n"),
+    )
+  }
+
+  /// Run the editor in its own process group and collect its standard
+  /// output, see `wait`.
+  pub fn output(&self, mut command: Cmd, timeout: Duration) -> Result<Vec<u8>> {
+    command.stdout(Stdio::piped());
+
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+
+    let mut child = command.spawn().context(error::StartupTimeSnafu)?;
+
+    let stdout = Self::read(child.stdout.take());
+
+    self.wait(&mut child, stdout, timeout)
+  }
+
+  /// Read an output stream of a started editor to the end on its own
+  /// thread.
+  pub fn read(
+    stream: Option<impl Read + Send + 'static>,
+  ) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+      let mut output = Vec::new();
+
+      if let Some(mut stream) = stream {
+        stream.read_to_end(&mut output).ok();
+      }
+
+      output
+    })
+  }
+
+  /// Wait for a started editor to exit, killing it if it takes longer than
+  /// the timeout, e.g when it waits at a "Press ENTER" prompt, and return
+  /// what was read from it unless it fails, with its messages.
+  ///
+  /// Whatever is left in the process group of the editor is killed once it
+  /// exits, e.g a background job that still holds its output open. Output
+  /// that can't be read before the timeout runs out is dropped.
+  pub fn wait(
+    &self,
+    child: &mut Child,
+    output: JoinHandle<Vec<u8>>,
+    timeout: Duration,
+  ) -> Result<Vec<u8>> {
+    let deadline = Instant::now() + timeout;
+
+    let status = loop {
+      if let Some(status) = child.try_wait()? {
+        Self::kill(child)?;
+        break Some(status);
+      }
+
+      if Instant::now() >= deadline {
+        Self::kill(child)?;
+        child.wait()?;
+        break None;
+      }
+
+      thread::sleep(Duration::from_millis(5));
+    };
+
+    // Leave the output of a killed editor a moment to be read.
+    let deadline = deadline.max(Instant::now() + Self::GRACE);
+
+    while !output.is_finished() && Instant::now() < deadline {
+      thread::sleep(Duration::from_millis(5));
+    }
+
+    let output = if output.is_finished() {
+      output.join().unwrap_or_default()
+    } else {
+      Vec::new()
+    };
+
+    match status {
+      Some(status) if status.success() => Ok(output),
+      Some(status) => Err(Error::EditorExit {
+        command: self.to_string(),
+        messages: Self::messages(&output)?,
+        status,
+      }),
+      None => Err(Error::Timeout {
+        command: self.to_string(),
+        messages: Self::messages(&output)?,
+        timeout,
+      }),
+    }
+  }
+}
+
+impl Display for Editor {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.program)?;
+
+    for argument in &self.arguments {
+      write!(f, " {argument}")?;
+    }
+
+    Ok(())
+  }
+}
+
+impl From<Command> for Editor {
+  fn from(command: Command) -> Self {
+    Self {
+      arguments: Vec::new(),
+      program: command.to_string(),
+      command,
+      version: String::new(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[cfg(unix)]
+  use std::os::unix::fs::PermissionsExt;
+
+  #[cfg(unix)]
+  fn stub(directory: &Path, version: &str) -> Result<PathBuf> {
+    let path = directory.join("editor");
+
+    fs::write(
+      &path,
+      format!(
+        "#!/bin/sh\necho \"$@\" > '{}/arguments'\necho '{version}'\n",
+        directory.display()
+      ),
+    )?;
+
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+
+    Ok(path)
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn detect() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+
+    let path = stub(tempdir.path(), "NVIM v0.10.0")?;
+
+    let editor = Editor::detect(&format!("{} -v", path.display()))?;
+
+    assert_eq!(
+      editor,
+      Editor {
+        arguments: vec![String::from("-v")],
+        command: Command::Neovim,
+        program: path.display().to_string(),
+        version: String::from("NVIM v0.10.0"),
+      }
+    );
+
+    assert_eq!(
+      fs::read_to_string(tempdir.path().join("arguments"))?,
+      "-v --version\n"
+    );
+
+    let path = stub(
+      tempdir.path(),
+      "VIM - Vi IMproved 9.1 (2024 Jan 02, compiled Jan 01 1970 00:00:00)",
+    )?;
+
+    assert_eq!(
+      Editor::detect(&path.display().to_string())?.command,
+      Command::Vim
+    );
+
+    let path = stub(tempdir.path(), "GNU Emacs 29.1")?;
+
+    assert!(matches!(
+      Editor::detect(&path.display().to_string()),
+      Err(Error::UnknownEditor { version, .. }) if version == "GNU Emacs 29.1"
+    ));
+
+    let directory = tempdir.path().join("dir with space");
+
+    fs::create_dir(&directory)?;
+
+    let path = stub(&directory, "NVIM v0.10.0")?;
+
+    assert_eq!(
+      Editor::detect(&path.display().to_string())?.program,
+      path.display().to_string()
+    );
+
+    assert!(matches!(
+      Editor::detect(" "),
+      Err(Error::InvalidCommand { .. })
+    ));
+
+    Ok(())
+  }
+
+  #[test]
+  fn messages() -> Result<()> {
+    let output = concat!(
+      "Error detected while processing /tmp/bad.vim:\x1b[?25l\x1b[J\r\r\n",
+      "line    1:\x1b[J\r\r\n",
+      "E492: Not an editor command: foo bar\x1b[J\r\n",
+      "Press ENTER or type command to continue\x1b[?25h\x1b[?1049h",
+      "\x1b[22;0;0t\x1b[>4;2m\x1b[?1h\x1b=\x1b[H\x1b[2J\x1b[24;1H~",
+    );
+
+    assert_eq!(
+      Editor::messages(output.as_bytes())?,
+      dedent(
+        "
+        Error detected while processing /tmp/bad.vim:
+        line    1:
+        E492: Not an editor command: foo bar
+        Press ENTER or type command to continue"
+      )
+      .trim_start()
+    );
+
+    Ok(())
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn wait() -> Result<()> {
+    let editor = Editor::from(Command::Vim);
+
+    let run = |script: &str| {
+      let mut command = Cmd::new("sh");
+      command.arg("-c").arg(script);
+      std::os::unix::process::CommandExt::process_group(&mut command, 0);
+      let (mut child, output) = Terminal::default().spawn(command)?;
+      editor.wait(&mut child, output, Duration::from_millis(100))
+    };
+
+    run("echo fine")?;
+
+    let start = Instant::now();
+
+    run("sleep 8 & echo fine")?;
+
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    assert_eq!(
+      editor.output(
+        {
+          let mut command = Cmd::new("sh");
+          command.arg("-c").arg("sleep 8 & echo fine");
+          command
+        },
+        Duration::from_secs(5),
+      )?,
+      b"fine\n"
+    );
+
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    assert!(matches!(
+      run("echo 'E484: Cannot open file' >&2; exit 3"),
+      Err(Error::EditorExit { messages, status, .. })
+        if messages == "E484: Cannot open file" && status.code() == Some(3)
+    ));
+
+    assert!(matches!(
+      run("echo 'Press ENTER'; sleep 5"),
+      Err(Error::Timeout { messages, .. }) if messages == "Press ENTER"
+    ));
+
+    Ok(())
+  }
+}
@@ -3,22 +3,133 @@ use super::*;
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub(crate)))]
 pub enum Error {
+  #[snafu(display(
+    "Startup budget exceeded:\n{}",
+    violations
+      .iter()
+      .map(|violation| format!("  - {violation}"))
+      .collect::<Vec<_>>()
+      .join("\n")
+  ))]
+  BudgetExceeded { violations: Vec<Violation> },
+  #[snafu(display("Unable to parse the budget file `{}`: {}", path.display(), source))]
+  BudgetFile {
+    path: PathBuf,
+    source: toml::de::Error,
+  },
+  #[snafu(display("Invalid budget pattern `{}`: {}", pattern, source))]
+  BudgetPattern {
+    pattern: String,
+    source: glob::PatternError,
+  },
+  #[snafu(display("Unable to find the config `{}`.", path.display()))]
+  ConfigNotFound { path: PathBuf },
   #[snafu(context(false), display("CSV Error: {}", source))]
   Csv { source: csv::Error },
+  #[snafu(display("The CSV file `{}` has no `{}` column.", path.display(), column))]
+  CsvColumn { column: String, path: PathBuf },
+  #[snafu(display(
+    "`{}` exited with {}{}",
+    command,
+    status
+      .code()
+      .map_or_else(|| status.to_string(), |code| format!("status {code}")),
+    if messages.is_empty() { String::new() } else { format!(":\n{messages}") }
+  ))]
+  EditorExit {
+    command: String,
+    messages: String,
+    status: process::ExitStatus,
+  },
+  #[snafu(display("Failed to run `{} --version`: {}", program, source))]
+  EditorVersion { program: String, source: io::Error },
+  #[snafu(display(
+    "No runs were recorded in `{}` yet, record one with `--record`.",
+    path.display()
+  ))]
+  EmptyHistory { path: PathBuf },
+  #[snafu(display("`{}` has no times in `{}`.", name, path.display()))]
+  EmptyTimes { name: String, path: PathBuf },
+  #[snafu(display(
+    "Unable to find a data directory for the history, set `$XDG_DATA_HOME` or \
+     use `--history-file`."
+  ))]
+  HistoryPath,
   #[snafu(display("Invalid command: {}", cmd))]
   InvalidCommand { cmd: String },
+  #[snafu(display(
+    "Invalid dependency `{}`, expected `plugin=dependency,...`.",
+    value
+  ))]
+  InvalidDependency { value: String },
+  #[snafu(display("Invalid estimator: {}", estimator))]
+  InvalidEstimator { estimator: String },
+  #[snafu(display("Invalid confidence interval method: {}", interval))]
+  InvalidInterval { interval: String },
+  #[snafu(display("Invalid outlier rejection method: {}", method))]
+  InvalidOutliers { method: String },
+  #[snafu(display("Invalid significance test: {}", test))]
+  InvalidSignificance { test: String },
+  #[snafu(display(
+    "Invalid terminal size `{}`, expected `<columns>x<rows>`.",
+    size
+  ))]
+  InvalidTerminal { size: String },
+  #[snafu(display("Invalid timing mode: {}", mode))]
+  InvalidTimingMode { mode: String },
   #[snafu(context(false), display("IO Error: {}", source))]
   Io { source: io::Error },
+  #[snafu(context(false), display("JSON Error: {}", source))]
+  Json { source: serde_json::Error },
+  #[snafu(display("The startup log has no total startup time."))]
+  MissingTotal,
   #[snafu(context(false), display("Parse float error: {}", source))]
   ParseFloat { source: num::ParseFloatError },
   #[snafu(display("Unable to find a vim plugin directory."))]
   PluginDirectory,
-  #[snafu(display("Unable to read the contents of the `vim.log` file."))]
-  ReadLog { source: io::Error },
+  #[cfg(unix)]
+  #[snafu(display("Failed to open a pseudo-terminal: {}", source))]
+  PseudoTerminal { source: nix::Error },
+  #[snafu(display("Unable to read the startup log `{}`.", path.display()))]
+  ReadLog { path: PathBuf, source: io::Error },
   #[snafu(context(false), display("Regex Error: {}", source))]
   Regex { source: regex::Error },
-  #[snafu(display("Unable to remove `vim.log` file."))]
-  RemoveLog { source: io::Error },
+  #[snafu(display("Unable to remove the startup log `{}`.", path.display()))]
+  RemoveLog { path: PathBuf, source: io::Error },
   #[snafu(display("Failed to execute vim startuptime."))]
   StartupTime { source: io::Error },
+  #[snafu(display("Unable to create a temporary directory for the logs."))]
+  TempDir { source: io::Error },
+  #[snafu(display(
+    "`{}` didn't exit within {} seconds, e.g because it's waiting at a \
+     prompt{}",
+    command,
+    timeout.as_secs_f64(),
+    if messages.is_empty() { String::new() } else { format!(":\n{messages}") }
+  ))]
+  Timeout {
+    command: String,
+    messages: String,
+    timeout: Duration,
+  },
+  #[snafu(display(
+    "`{}` is neither Vim nor Neovim, its version is `{}`.",
+    program,
+    version
+  ))]
+  UnknownEditor { program: String, version: String },
+  #[snafu(display("No files were sourced for plugin `{}`.", plugin))]
+  UnknownPlugin { plugin: String },
+}
+
+impl Error {
+  /// The exit status of the program when it fails with this error, so CI
+  /// can tell a blown budget apart from a failure to measure or a usage
+  /// error, which clap exits with 2.
+  pub(crate) fn code(&self) -> i32 {
+    match self {
+      Error::BudgetExceeded { .. } => 3,
+      _ => 1,
+    }
+  }
 }
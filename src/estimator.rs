@@ -0,0 +1,55 @@
+use super::*;
+
+/// How the times of a plugin are summarized into a single value, which is
+/// used to rank, print and plot plugins.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) enum Estimator {
+  /// The arithmetic mean.
+  #[default]
+  Mean,
+  /// The middle value.
+  Median,
+  /// The mean of the times left after dropping the lowest and highest ones.
+  Trimmed,
+  /// The mean of the times after clamping the lowest and highest ones.
+  Winsorized,
+}
+
+impl Display for Estimator {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Estimator::Mean => write!(f, "mean"),
+      Estimator::Median => write!(f, "median"),
+      Estimator::Trimmed => write!(f, "trimmed"),
+      Estimator::Winsorized => write!(f, "winsorized"),
+    }
+  }
+}
+
+impl Estimator {
+  /// The value of a plugin according to this estimator.
+  pub fn estimate(self, plugin: &Plugin) -> f64 {
+    match self {
+      Estimator::Mean => plugin.average(),
+      Estimator::Median => plugin.median(),
+      Estimator::Trimmed => plugin.trimmed_mean(),
+      Estimator::Winsorized => plugin.winsorized_mean(),
+    }
+  }
+}
+
+impl FromStr for Estimator {
+  type Err = Error;
+
+  fn from_str(value: &str) -> Result<Self> {
+    match value {
+      "mean" | "average" => Ok(Estimator::Mean),
+      "median" => Ok(Estimator::Median),
+      "trimmed" | "trimmed-mean" => Ok(Estimator::Trimmed),
+      "winsorized" | "winsorized-mean" => Ok(Estimator::Winsorized),
+      _ => Err(Error::InvalidEstimator {
+        estimator: value.to_owned(),
+      }),
+    }
+  }
+}
@@ -1,25 +1,92 @@
 use super::*;
 
-pub(crate) fn write(path: PathBuf, plugins: &[Plugin]) -> Result<(), Error> {
+pub(crate) fn write(
+  path: PathBuf,
+  report: &Report,
+  phases: bool,
+) -> Result<(), Error> {
   let mut writer = Writer::from_path(path.clone())?;
 
-  writer.write_record([
-    "Plugin",
-    "Max",
-    "Min",
-    "Median",
-    "Average",
-    "Deviation",
-  ])?;
+  writer.write_record(
+    iter::once(String::from("Kind"))
+      .chain(iter::once(String::from("Plugin")))
+      .chain(
+        [
+          "Max",
+          "Min",
+          "Median",
+          "Average",
+          "Deviation",
+          "Trimmed mean",
+          "Winsorized mean",
+          "MAD",
+          "P90",
+          "P95",
+          "P99",
+          "T CI low",
+          "T CI high",
+          "Bootstrap CI low",
+          "Bootstrap CI high",
+        ]
+        .iter()
+        .map(|column| format!("{column} ({})", report.run.timing)),
+      )
+      .chain(iter::once(String::from("Discarded")))
+      .chain(iter::once(String::from("Budget")))
+      .chain(iter::once(String::from("Over budget")))
+      .chain(iter::once(String::from("Times")))
+      .chain(iter::once(String::from("Version"))),
+  )?;
+
+  let rows = report
+    .plugins
+    .iter()
+    .map(|plugin| ("plugin", plugin))
+    .chain(
+      report
+        .phases
+        .iter()
+        .filter(|_| phases)
+        .map(|phase| ("phase", phase)),
+    )
+    .chain(report.total.iter().map(|total| ("total", total)))
+    .chain(report.warmup.iter().map(|warmup| ("warmup", warmup)));
+
+  for (kind, plugin) in rows {
+    let (t_interval, bootstrap_interval) =
+      (plugin.t_interval(), plugin.bootstrap_interval());
 
-  for plugin in plugins {
     writer.write_record(&[
+      kind.to_owned(),
       plugin.name.clone(),
       format!("{:.5}", plugin.max()),
       format!("{:.5}", plugin.min()),
       format!("{:.5}", plugin.median()),
       format!("{:.5}", plugin.average()),
       format!("{:.5}", plugin.deviation()),
+      format!("{:.5}", plugin.trimmed_mean()),
+      format!("{:.5}", plugin.winsorized_mean()),
+      format!("{:.5}", plugin.mad()),
+      format!("{:.5}", plugin.percentile(90.0)),
+      format!("{:.5}", plugin.percentile(95.0)),
+      format!("{:.5}", plugin.percentile(99.0)),
+      format!("{:.5}", t_interval.0),
+      format!("{:.5}", t_interval.1),
+      format!("{:.5}", bootstrap_interval.0),
+      format!("{:.5}", bootstrap_interval.1),
+      plugin.discarded.to_string(),
+      plugin
+        .budget
+        .map(|budget| format!("{budget:.5}"))
+        .unwrap_or_default(),
+      plugin.over_budget(report.run.estimator).to_string(),
+      plugin
+        .times
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" "),
+      report.run.version.clone(),
     ])?;
   }
 
@@ -30,15 +97,117 @@ pub(crate) fn write(path: PathBuf, plugins: &[Plugin]) -> Result<(), Error> {
   Ok(())
 }
 
-pub(crate) fn plot(path: PathBuf, plugins: &[Plugin]) -> Result<(), Error> {
-  let plugins = plugins.to_vec();
+/// Write the sourcing trees in the folded stack format understood by
+/// flamegraph tools, e.g:
+///
+/// sourcing vimrc file(s);/home/.vimrc;/home/.vim/plugged/ale/plugin/ale.vim 2311
+///
+/// Each stack is followed by its self time in microseconds, averaged over all
+/// iterations, sorted by stack.
+pub(crate) fn folded(path: PathBuf, report: &Report) -> Result<(), Error> {
+  let mut stacks = BTreeMap::<String, f64>::new();
+
+  for tree in &report.trees {
+    for (stack, time) in tree.folded() {
+      *stacks.entry(stack).or_default() += time;
+    }
+  }
+
+  let iterations = f64::from(u32::try_from(report.trees.len()).unwrap_or(1));
+
+  let mut file = fs::File::create(&path)?;
+
+  for (stack, time) in stacks {
+    let micros = (time * 1000.0 / iterations).round();
+
+    if micros >= 1.0 {
+      writeln!(file, "{stack} {micros}")?;
+    }
+  }
+
+  info!("Folded stacks written to `{}`", path.display());
+
+  Ok(())
+}
+
+/// Write the report as a single JSON document, see `Document`.
+pub(crate) fn json(path: PathBuf, report: &Report) -> Result<(), Error> {
+  let mut writer = output(&path)?;
+
+  serde_json::to_writer_pretty(&mut writer, &Document::from(report))?;
+  writeln!(writer)?;
+
+  info!("JSON written to `{}`", path.display());
+
+  Ok(())
+}
+
+/// Write the report as newline delimited JSON, one `Record` per line.
+pub(crate) fn ndjson(path: PathBuf, report: &Report) -> Result<(), Error> {
+  let mut writer = output(&path)?;
+
+  for record in Record::all(report) {
+    serde_json::to_writer(&mut writer, &record)?;
+    writeln!(writer)?;
+  }
+
+  info!("NDJSON written to `{}`", path.display());
+
+  Ok(())
+}
+
+/// Open `path` for writing, where `-` stands for standard output.
+fn output(path: &Path) -> Result<Box<dyn Write>, Error> {
+  if path == Path::new("-") {
+    Ok(Box::new(io::stdout()))
+  } else {
+    Ok(Box::new(io::BufWriter::new(fs::File::create(path)?)))
+  }
+}
+
+/// A point of the total startup time reference line.
+struct Reference {
+  name: String,
+  time: f32,
+}
+
+impl PointDatum<f32, String> for Reference {
+  fn get_key(&self) -> String {
+    String::from("Total startup time")
+  }
+
+  fn get_x(&self) -> f32 {
+    self.time
+  }
+
+  fn get_y(&self) -> String {
+    self.name.clone()
+  }
+}
+
+pub(crate) fn plot(path: PathBuf, report: &Report) -> Result<(), Error> {
+  let plugins = report.plugins.clone();
+
+  let total = report.total.as_ref().map(|total| {
+    report
+      .run
+      .estimator
+      .estimate(total)
+      .to_f32()
+      .unwrap_or_default()
+  });
 
   let (width, height, top, right, bottom, left) = (1200, 800, 90, 10, 50, 120);
 
   let x = ScaleLinear::new()
     .set_domain(vec![
-      (plugins.min() - 0.05).to_f32().unwrap_or_default(),
-      (plugins.max() + 1.0).to_f32().unwrap_or_default(),
+      (plugins.min(report.run.estimator) - 0.05)
+        .to_f32()
+        .unwrap_or_default(),
+      (plugins.max(report.run.estimator) + 1.0)
+        .to_f32()
+        .unwrap_or_default()
+        .max(total.unwrap_or_default() + 1.0),
     ])
     .set_range(vec![0, width - left - right]);
 
@@ -46,28 +215,84 @@ pub(crate) fn plot(path: PathBuf, plugins: &[Plugin]) -> Result<(), Error> {
     .set_domain(plugins.iter().map(|plugin| plugin.name.clone()).collect())
     .set_range(vec![0, height - top - bottom]);
 
+  // Plugins are split into two series when a budget file is in use, so the
+  // ones over budget stand out.
+  let budgeted = plugins.iter().any(|plugin| plugin.budget.is_some());
+
+  let keys = if budgeted {
+    vec![String::from("Within budget"), String::from("Over budget")]
+  } else {
+    vec![String::new()]
+  };
+
   let view = HorizontalBarView::new()
     .set_x_scale(&x)
     .set_y_scale(&y)
+    .set_keys(keys.clone())
     .load_data(
       &plugins
         .iter()
         .map(|plugin| {
           (
             plugin.name.clone(),
-            plugin.average().to_f32().unwrap_or_default(),
+            report
+              .run
+              .estimator
+              .estimate(plugin)
+              .to_f32()
+              .unwrap_or_default(),
+            keys[usize::from(plugin.over_budget(report.run.estimator))].clone(),
           )
         })
-        .collect::<Vec<(String, f32)>>(),
+        .collect::<Vec<(String, f32, String)>>(),
     )
     .unwrap();
 
-  Chart::new()
+  let reference = total.map(|time| {
+    LineSeriesView::new()
+      .set_x_scale(&x)
+      .set_y_scale(&y)
+      .set_marker_type(MarkerType::Circle)
+      .set_colors(Color::from_vec_of_hex_strings(vec!["#d62728"]))
+      .set_label_visibility(false)
+      .load_data(
+        &[plugins.first(), plugins.last()]
+          .iter()
+          .flatten()
+          .map(|plugin| Reference {
+            name: plugin.name.clone(),
+            time,
+          })
+          .collect::<Vec<Reference>>(),
+      )
+      .unwrap()
+  });
+
+  let mut chart = Chart::new()
     .set_width(width)
     .set_height(height)
     .set_margins(top, right, bottom, left)
-    .add_title(String::from("Vim Plugin Start Times"))
-    .add_view(&view)
+    .add_title(format!(
+      "Vim Plugin Start Times ({}, {}){}",
+      report.run.timing,
+      report.run.estimator,
+      if report.run.version.is_empty() {
+        String::new()
+      } else {
+        format!(" - {}", report.run.version)
+      }
+    ))
+    .add_view(&view);
+
+  if let Some(reference) = &reference {
+    chart = chart.add_view(reference);
+  }
+
+  if reference.is_some() || budgeted {
+    chart = chart.add_legend_at(AxisPosition::Top);
+  }
+
+  chart
     .add_axis_bottom(&x)
     .add_axis_top(&x)
     .add_axis_left(&y)
@@ -79,3 +304,127 @@ pub(crate) fn plot(path: PathBuf, plugins: &[Plugin]) -> Result<(), Error> {
 
   Ok(())
 }
+
+/// Write the sourcing trees as a Chrome trace, which can be opened in
+/// Perfetto, speedscope or `chrome://tracing`.
+///
+/// Every phase, script and require becomes a complete event positioned at
+/// its clock offset, with each iteration laid out on its own thread.
+pub(crate) fn trace(path: PathBuf, report: &Report) -> Result<(), Error> {
+  let mut events = Vec::new();
+
+  for (i, tree) in report.trees.iter().enumerate() {
+    let tid = i + 1;
+
+    events.push(json!({
+      "name": "thread_name",
+      "ph": "M",
+      "pid": 1,
+      "tid": tid,
+      "args": { "name": format!("iteration {tid}") },
+    }));
+
+    for node in tree.nodes() {
+      events.push(json!({
+        "name": node.frame(),
+        "cat": node.category(),
+        "ph": "X",
+        "ts": (node.start * 1000.0).round(),
+        "dur": ((node.end - node.start) * 1000.0).round(),
+        "pid": 1,
+        "tid": tid,
+        "args": { "self": (node.exclusive() * 1000.0).round() / 1000.0 },
+      }));
+    }
+  }
+
+  serde_json::to_writer(
+    fs::File::create(&path)?,
+    &json!({ "traceEvents": events, "displayTimeUnit": "ms" }),
+  )?;
+
+  info!("Trace written to `{}`", path.display());
+
+  Ok(())
+}
+
+/// Plot the total startup time and the times of the slowest plugins of the
+/// latest run across every recorded run, as a SVG line chart.
+pub(crate) fn trend(
+  path: PathBuf,
+  recordings: &[Recording],
+  estimator: Estimator,
+  count: Option<usize>,
+) -> Result<(), Error> {
+  let names = iter::once(String::from("total"))
+    .chain(
+      recordings
+        .last()
+        .map(|recording| recording.slowest(estimator, count.unwrap_or(5)))
+        .unwrap_or_default(),
+    )
+    .collect::<Vec<String>>();
+
+  let data = recordings
+    .iter()
+    .enumerate()
+    .flat_map(|(i, recording)| {
+      names.iter().filter_map(move |name| {
+        let plugin = if name == "total" {
+          recording.total()
+        } else {
+          recording.plugin(name)
+        }?;
+
+        Some((
+          (i + 1).to_f32().unwrap_or_default(),
+          estimator.estimate(&plugin).to_f32().unwrap_or_default(),
+          name.clone(),
+        ))
+      })
+    })
+    .collect::<Vec<(f32, f32, String)>>();
+
+  let (width, height, top, right, bottom, left) = (1200, 800, 90, 40, 50, 80);
+
+  let x = ScaleLinear::new()
+    .set_domain(vec![
+      1.0,
+      recordings.len().max(2).to_f32().unwrap_or_default(),
+    ])
+    .set_range(vec![0, width - left - right]);
+
+  let y = ScaleLinear::new()
+    .set_domain(vec![
+      0.0,
+      data.iter().map(|point| point.1).fold(1.0, f32::max) * 1.1,
+    ])
+    .set_range(vec![height - top - bottom, 0]);
+
+  let view = LineSeriesView::new()
+    .set_x_scale(&x)
+    .set_y_scale(&y)
+    .set_marker_type(MarkerType::Circle)
+    .set_label_visibility(false)
+    .set_keys(names)
+    .load_data(&data)
+    .unwrap();
+
+  Chart::new()
+    .set_width(width)
+    .set_height(height)
+    .set_margins(top, right, bottom, left)
+    .add_title(format!("Vim Startup Time Trends ({estimator})"))
+    .add_view(&view)
+    .add_legend_at(AxisPosition::Top)
+    .add_axis_bottom(&x)
+    .add_axis_left(&y)
+    .add_bottom_axis_label("Run")
+    .add_left_axis_label("Time")
+    .save(path.clone())
+    .unwrap();
+
+  info!("Trend plot saved to `{}`", path.display());
+
+  Ok(())
+}
@@ -0,0 +1,119 @@
+use super::*;
+
+/// The runs recorded with `--record`, stored one per line as JSON.
+#[derive(Debug, Clone)]
+pub(crate) struct History {
+  pub path: PathBuf,
+}
+
+impl History {
+  /// Append a report to the history, creating it if needed.
+  pub fn append(&self, report: &Report) -> Result<()> {
+    if let Some(parent) = self.path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+
+    let mut file = fs::OpenOptions::new()
+      .create(true)
+      .append(true)
+      .open(&self.path)?;
+
+    serde_json::to_writer(
+      &mut file,
+      &Recording {
+        document: Document::from(report),
+        timestamp: SystemTime::now()
+          .duration_since(UNIX_EPOCH)
+          .map(|duration| duration.as_secs())
+          .unwrap_or_default(),
+      },
+    )?;
+
+    writeln!(file)?;
+
+    info!("Run recorded to `{}`", self.path.display());
+
+    Ok(())
+  }
+
+  /// The history under the XDG data directory, i.e
+  /// `$XDG_DATA_HOME/vim-profiler/history.jsonl`, which defaults to
+  /// `~/.local/share`.
+  pub fn default_path() -> Option<PathBuf> {
+    env::var_os("XDG_DATA_HOME")
+      .filter(|directory| !directory.is_empty())
+      .map(PathBuf::from)
+      .or_else(|| {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share"))
+      })
+      .map(|directory| directory.join("vim-profiler").join("history.jsonl"))
+  }
+
+  /// Every recorded run, oldest first.
+  pub fn load(&self) -> Result<Vec<Recording>> {
+    if !self.path.is_file() {
+      return Err(Error::EmptyHistory {
+        path: self.path.clone(),
+      });
+    }
+
+    let recordings = fs::read_to_string(&self.path)?
+      .lines()
+      .filter(|line| !line.trim().is_empty())
+      .map(serde_json::from_str)
+      .collect::<Result<Vec<Recording>, _>>()?;
+
+    if recordings.is_empty() {
+      return Err(Error::EmptyHistory {
+        path: self.path.clone(),
+      });
+    }
+
+    Ok(recordings)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn append() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+
+    let history = History {
+      path: tempdir.path().join("vim-profiler/history.jsonl"),
+    };
+
+    assert!(matches!(history.load(), Err(Error::EmptyHistory { .. })));
+
+    let report = Report::new(
+      &[Sample {
+        plugins: HashMap::from([("vim-just".into(), 1.5)]),
+        total: Some(20.0),
+        ..Sample::default()
+      }],
+      &[],
+      Run::default(),
+    );
+
+    history.append(&report)?;
+    history.append(&report)?;
+
+    let recordings = history.load()?;
+
+    assert_eq!(recordings.len(), 2);
+    assert_eq!(recordings[0].document, Document::from(&report));
+    assert_eq!(
+      recordings[1].plugin("vim-just").map(|plugin| plugin.times),
+      Some(vec![1.5])
+    );
+    assert_eq!(
+      recordings[1].total().map(|total| total.times),
+      Some(vec![20.0])
+    );
+    assert!(recordings[0].timestamp > 0);
+
+    Ok(())
+  }
+}
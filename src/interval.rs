@@ -0,0 +1,47 @@
+use super::*;
+
+/// How the confidence interval of the mean of a plugin is computed.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) enum Interval {
+  /// The percentiles of the means of resampled times, which makes no
+  /// assumption about how the times are distributed.
+  Bootstrap,
+  /// Student's t distribution, which assumes the times are roughly normally
+  /// distributed.
+  #[default]
+  T,
+}
+
+impl Display for Interval {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Interval::Bootstrap => write!(f, "bootstrap"),
+      Interval::T => write!(f, "t"),
+    }
+  }
+}
+
+impl FromStr for Interval {
+  type Err = Error;
+
+  fn from_str(value: &str) -> Result<Self> {
+    match value {
+      "bootstrap" => Ok(Interval::Bootstrap),
+      "t" => Ok(Interval::T),
+      _ => Err(Error::InvalidInterval {
+        interval: value.to_owned(),
+      }),
+    }
+  }
+}
+
+impl Interval {
+  /// The confidence interval of the mean of a plugin according to this
+  /// method.
+  pub fn compute(self, plugin: &Plugin) -> (f64, f64) {
+    match self {
+      Interval::Bootstrap => plugin.bootstrap_interval(),
+      Interval::T => plugin.t_interval(),
+    }
+  }
+}
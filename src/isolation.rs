@@ -0,0 +1,231 @@
+use super::*;
+
+/// The standalone cost of each plugin, measured by starting the editor once
+/// per plugin without any config and with only that plugin and its
+/// dependencies on the `runtimepath`.
+#[derive(Debug, Clone)]
+pub(crate) struct Isolation {
+  /// The run with the whole config.
+  pub config: Report,
+  pub plugins: Vec<Standalone>,
+  pub test: Significance,
+}
+
+impl Isolation {
+  /// The editor arguments that skip every initialization file, as with
+  /// `-u NONE`, while still loading the plugins on the `runtimepath`, which
+  /// only holds the editor's own runtime files and `paths`.
+  pub fn arguments(paths: &[&Path]) -> Vec<String> {
+    let paths = paths
+      .iter()
+      .map(|path| escape(&path.display().to_string()))
+      .collect::<Vec<_>>();
+
+    let command = if paths.is_empty() {
+      String::from("set loadplugins")
+    } else {
+      format!(
+        "set loadplugins | set runtimepath^={} | set runtimepath+={}",
+        paths.join(","),
+        paths
+          .iter()
+          .map(|path| format!("{path}/after"))
+          .collect::<Vec<_>>()
+          .join(",")
+      )
+    };
+
+    vec![String::from("--clean"), String::from("--cmd"), command]
+  }
+
+  /// Measure the standalone cost of every plugin of a config report that
+  /// has a plugin directory, where `run` starts the editor with extra
+  /// arguments and plugin directories.
+  ///
+  /// Every run is given the plugin directories of the config, since the
+  /// run without any plugin has none in its log.
+  ///
+  /// The dependencies of a plugin are looked up by name among the plugins
+  /// of the config.
+  pub fn measure(
+    config: Report,
+    dependencies: &[Dependency],
+    test: Significance,
+    run: impl Fn(Vec<String>, Vec<PathBuf>) -> Result<Report>,
+  ) -> Result<Self> {
+    let mut directories = config
+      .plugins
+      .iter()
+      .filter_map(|plugin| plugin.path.as_deref())
+      .filter(|path| !path.is_file())
+      .filter_map(Path::parent)
+      .map(Path::to_path_buf)
+      .collect::<Vec<_>>();
+
+    directories.sort();
+    directories.dedup();
+
+    info!("Measuring startup without any plugin ...");
+
+    let clean = run(Self::arguments(&[]), directories.clone())?
+      .total
+      .ok_or(Error::MissingTotal)?;
+
+    let path = |name: &str| {
+      config
+        .plugins
+        .iter()
+        .find(|plugin| plugin.name == name)
+        .and_then(|plugin| plugin.path.as_deref())
+        .ok_or_else(|| Error::UnknownPlugin {
+          plugin: name.to_owned(),
+        })
+    };
+
+    let mut plugins = Vec::new();
+
+    for plugin in &config.plugins {
+      let Some(directory) = plugin.path.as_deref() else {
+        continue;
+      };
+
+      // System scripts are attributed to the script itself rather than to a
+      // plugin directory that could be put on the runtimepath.
+      if directory.is_file() {
+        continue;
+      }
+
+      let mut paths = vec![directory];
+
+      for dependency in dependencies
+        .iter()
+        .filter(|dependency| dependency.plugin == plugin.name)
+        .flat_map(|dependency| &dependency.dependencies)
+      {
+        paths.push(path(dependency)?);
+      }
+
+      info!("Measuring startup with only `{}` ...", plugin.name);
+
+      let report = run(Self::arguments(&paths), directories.clone())?;
+
+      plugins.push(Standalone {
+        attributed: report
+          .plugins
+          .iter()
+          .find(|isolated| isolated.name == plugin.name)
+          .cloned(),
+        cost: Change::new(
+          plugin.name.clone(),
+          Some(clean.clone()),
+          Some(report.total.ok_or(Error::MissingTotal)?),
+          test,
+        ),
+      });
+    }
+
+    Ok(Self {
+      config,
+      plugins,
+      test,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn arguments() {
+    assert_eq!(
+      Isolation::arguments(&[]),
+      vec!["--clean", "--cmd", "set loadplugins"]
+    );
+
+    assert_eq!(
+      Isolation::arguments(&[
+        Path::new("/plugged/a b"),
+        Path::new("/plugged/c")
+      ]),
+      vec![
+        "--clean",
+        "--cmd",
+        "set loadplugins | set runtimepath^=/plugged/a\\ b,/plugged/c | set \
+         runtimepath+=/plugged/a\\ b/after,/plugged/c/after"
+      ]
+    );
+  }
+
+  #[test]
+  fn measure() -> Result<()> {
+    let config = Report::single(
+      50.0,
+      &[
+        ("telescope.nvim", Some("/plugged/telescope.nvim")),
+        ("plenary.nvim", Some("/plugged/plenary.nvim")),
+      ],
+    );
+
+    let dependencies = vec!["telescope.nvim=plenary.nvim".parse()?];
+
+    let isolation = Isolation::measure(
+      config.clone(),
+      &dependencies,
+      Significance::Welch,
+      |arguments, directories| {
+        assert_eq!(directories, vec![PathBuf::from("/plugged")]);
+
+        let command = &arguments[2];
+
+        Ok(if command.contains("telescope") {
+          assert!(
+            command.contains("/plugged/telescope.nvim,/plugged/plenary.nvim")
+          );
+          Report::single(
+            30.0,
+            &[("telescope.nvim", None), ("plenary.nvim", None)],
+          )
+        } else if command.contains("plenary") {
+          Report::single(22.0, &[("plenary.nvim", None)])
+        } else {
+          Report::single(20.0, &[])
+        })
+      },
+    )?;
+
+    let costs = isolation
+      .plugins
+      .iter()
+      .map(|standalone| {
+        (
+          standalone.cost.name.as_str(),
+          (
+            standalone.cost.delta(Estimator::Mean),
+            standalone.attributed.is_some(),
+          ),
+        )
+      })
+      .collect::<HashMap<_, _>>();
+
+    assert_eq!(
+      costs,
+      HashMap::from([
+        ("telescope.nvim", (10.0, true)),
+        ("plenary.nvim", (2.0, true))
+      ])
+    );
+
+    assert!(matches!(
+      Isolation::measure(
+        config,
+        &["telescope.nvim=unknown".parse()?],
+        Significance::Welch,
+        |_, _| Ok(Report::single(20.0, &[])),
+      ),
+      Err(Error::UnknownPlugin { plugin }) if plugin == "unknown"
+    ));
+
+    Ok(())
+  }
+}
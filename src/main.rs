@@ -1,44 +1,111 @@
 use {
+  ablation::Ablation,
   arguments::Arguments,
-  charts::{Chart, HorizontalBarView, ScaleBand, ScaleLinear},
+  budget::Budget,
+  budget_file::BudgetFile,
+  ceiling::Ceiling,
+  change::Change,
+  charts::{
+    AxisPosition, Chart, Color, HorizontalBarView, LineSeriesView, MarkerType,
+    PointDatum, ScaleBand, ScaleLinear,
+  },
   clap::Parser,
   command::Command,
-  csv::Writer,
+  comparison::Comparison,
+  csv::{Reader, Writer},
+  dependency::Dependency,
+  editor::Editor,
   env_logger::{self},
   error::Error,
-  export::{plot, write},
+  estimator::Estimator,
+  export::{folded, json, ndjson, plot, trace, trend, write},
+  history::History,
+  interval::Interval,
+  isolation::Isolation,
   log::info,
   num_traits::cast::ToPrimitive,
+  outliers::Outliers,
+  phase::Phase,
   plugin::Plugin,
   plugins::Plugins,
   printer::Printer,
+  recording::Recording,
   regex::RegexBuilder,
+  report::Report,
+  run::Run,
+  runtimepath::Runtimepath,
+  sample::Sample,
+  schema::{Document, Record},
+  serde::{Deserialize, Serialize},
+  serde_json::json,
+  significance::Significance,
   snafu::{ResultExt, Snafu},
+  snapshot::Snapshot,
+  standalone::Standalone,
+  startup_log::{Event, StartupLog, Timing},
+  statistics::{mann_whitney, quantile, t_quantile, welch, Rng},
   std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     convert::TryFrom,
     env,
     fmt::{self, Display, Formatter},
     fs,
-    io::{self},
+    io::{self, Read, Write},
     iter, num,
-    path::PathBuf,
+    path::{Path, PathBuf},
     process,
-    process::{Command as Cmd, Stdio},
+    process::{Child, Command as Cmd, Stdio},
     str::FromStr,
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
   },
-  utils::repeat,
+  subcommand::Subcommand,
+  terminal::Terminal,
+  timing_mode::TimingMode,
+  tree::Tree,
+  utils::{date, escape, repeat},
+  violation::Violation,
   worker::Worker,
 };
 
+mod ablation;
 mod arguments;
+mod budget;
+mod budget_file;
+mod ceiling;
+mod change;
 mod command;
+mod comparison;
+mod dependency;
+mod editor;
 mod error;
+mod estimator;
 mod export;
+mod history;
+mod interval;
+mod isolation;
+mod outliers;
+mod phase;
 mod plugin;
 mod plugins;
 mod printer;
+mod recording;
+mod report;
+mod run;
+mod runtimepath;
+mod sample;
+mod schema;
+mod significance;
+mod snapshot;
+mod standalone;
+mod startup_log;
+mod statistics;
+mod subcommand;
+mod terminal;
+mod timing_mode;
+mod tree;
 mod utils;
+mod violation;
 mod worker;
 
 pub(crate) type Result<T, E = Error> = std::result::Result<T, E>;
@@ -51,7 +118,7 @@ fn main() {
     Ok(()) => {}
     Err(e) => {
       eprintln!("{e}");
-      process::exit(1);
+      process::exit(e.code());
     }
   }
 }
@@ -0,0 +1,110 @@
+use super::*;
+
+/// How outlying times are detected and discarded before computing any
+/// statistic.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) enum Outliers {
+  /// Times further than 1.5 interquartile ranges from the first or third
+  /// quartile.
+  Iqr,
+  /// Times further than 3 scaled median absolute deviations from the median.
+  Mad,
+  /// Keep every time.
+  #[default]
+  None,
+}
+
+impl Display for Outliers {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Outliers::Iqr => write!(f, "iqr"),
+      Outliers::Mad => write!(f, "mad"),
+      Outliers::None => write!(f, "none"),
+    }
+  }
+}
+
+impl FromStr for Outliers {
+  type Err = Error;
+
+  fn from_str(value: &str) -> Result<Self> {
+    match value {
+      "iqr" => Ok(Outliers::Iqr),
+      "mad" => Ok(Outliers::Mad),
+      "none" => Ok(Outliers::None),
+      _ => Err(Error::InvalidOutliers {
+        method: value.to_owned(),
+      }),
+    }
+  }
+}
+
+impl Outliers {
+  /// The scale factor that makes the median absolute deviation a consistent
+  /// estimator of the standard deviation of normally distributed times.
+  const MAD_SCALE: f64 = 1.4826;
+
+  /// Discard the outlying times of a plugin, keeping track of how many were
+  /// discarded.
+  ///
+  /// Nothing is discarded when the times have no spread, e.g when there are
+  /// fewer than three of them or most of them are equal.
+  pub fn reject(self, plugin: &mut Plugin) {
+    let (low, high) = match self {
+      Outliers::Iqr => {
+        let (q1, q3) =
+          (quantile(&plugin.times, 0.25), quantile(&plugin.times, 0.75));
+        let iqr = q3 - q1;
+        (q1 - 1.5 * iqr, q3 + 1.5 * iqr)
+      }
+      Outliers::Mad => {
+        let (median, mad) = (plugin.median(), plugin.mad() * Self::MAD_SCALE);
+        (median - 3.0 * mad, median + 3.0 * mad)
+      }
+      Outliers::None => return,
+    };
+
+    if plugin.times.len() < 3 || high - low <= 0.0 {
+      return;
+    }
+
+    let len = plugin.times.len();
+
+    plugin.times.retain(|time| (low..=high).contains(time));
+
+    plugin.discarded += len - plugin.times.len();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reject() {
+    let times = vec![10.0, 10.5, 11.0, 9.5, 10.2, 48.0, 9.8];
+
+    let cases = vec![
+      (Outliers::None, 7, 0),
+      (Outliers::Iqr, 6, 1),
+      (Outliers::Mad, 6, 1),
+    ];
+
+    for (outliers, kept, discarded) in cases {
+      let mut plugin = Plugin::new("vim-just".into(), times.clone());
+
+      outliers.reject(&mut plugin);
+
+      assert_eq!(plugin.times.len(), kept);
+      assert_eq!(plugin.discarded, discarded);
+      assert!(!plugin.times.contains(&48.0) || outliers == Outliers::None);
+    }
+
+    let mut plugin =
+      Plugin::new("vim-just".into(), vec![1.0, 1.0, 1.0, 1.0, 9.0]);
+
+    Outliers::Iqr.reject(&mut plugin);
+
+    assert_eq!(plugin.discarded, 0);
+  }
+}
@@ -0,0 +1,151 @@
+use super::*;
+
+/// A coarse grained step of the editor startup sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Phase {
+  ArgumentParsing,
+  InitHighlight,
+  LoadingPlugins,
+  OpeningBuffers,
+  Other,
+  ReadingViminfo,
+  SourcingVimrc,
+  UiWait,
+  VimEnter,
+}
+
+impl Display for Phase {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Phase::ArgumentParsing => write!(f, "argument parsing"),
+      Phase::InitHighlight => write!(f, "init highlight"),
+      Phase::LoadingPlugins => write!(f, "loading plugins"),
+      Phase::OpeningBuffers => write!(f, "opening buffers"),
+      Phase::Other => write!(f, "other"),
+      Phase::ReadingViminfo => write!(f, "reading viminfo/shada"),
+      Phase::SourcingVimrc => write!(f, "sourcing vimrc"),
+      Phase::UiWait => write!(f, "UI wait"),
+      Phase::VimEnter => write!(f, "VimEnter autocommands"),
+    }
+  }
+}
+
+impl Phase {
+  /// Every phase, in the order they happen during startup.
+  pub const ALL: [Phase; 9] = [
+    Phase::ArgumentParsing,
+    Phase::InitHighlight,
+    Phase::UiWait,
+    Phase::SourcingVimrc,
+    Phase::LoadingPlugins,
+    Phase::ReadingViminfo,
+    Phase::OpeningBuffers,
+    Phase::VimEnter,
+    Phase::Other,
+  ];
+
+  /// Compute how long each phase took during a single startup.
+  ///
+  /// Two column events only report the time spent outside of any sourced
+  /// script, so the duration of an event is measured from the clock of the
+  /// previous two column event instead, e.g:
+  ///
+  /// 006.558  000.126: init highlight
+  /// 021.764  014.089  001.787: sourcing $VIMRUNTIME/defaults.vim
+  /// 021.767  000.120: sourcing vimrc file(s)
+  ///
+  /// Gives `sourcing vimrc` a duration of 15.209 milliseconds.
+  pub fn durations(log: &StartupLog) -> HashMap<Phase, f64> {
+    let mut durations = HashMap::new();
+
+    let mut previous = 0.0;
+
+    for event in &log.events {
+      if let Timing::Elapsed(_) = event.timing {
+        *durations
+          .entry(Self::from_description(&event.description))
+          .or_insert(0.0) += event.clock - previous;
+        previous = event.clock;
+      }
+    }
+
+    durations
+  }
+
+  /// Map the description of a two column event to its phase.
+  pub fn from_description(description: &str) -> Self {
+    match description {
+      "parsing arguments" | "expanding arguments" => Phase::ArgumentParsing,
+      "init highlight" => Phase::InitHighlight,
+      "loading plugins"
+      | "loading rtp plugins"
+      | "loading packages"
+      | "loading after plugins" => Phase::LoadingPlugins,
+      "opening buffers" => Phase::OpeningBuffers,
+      "reading viminfo" | "reading ShaDa" => Phase::ReadingViminfo,
+      "sourcing vimrc file(s)" => Phase::SourcingVimrc,
+      "waiting for UI" | "done waiting for UI" => Phase::UiWait,
+      "VimEnter autocommands" => Phase::VimEnter,
+      _ => Phase::Other,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn durations() -> Result<()> {
+    let content = r"
+      000.007  000.007: --- VIM STARTING ---
+      000.787  000.780: parsing arguments
+      000.800  000.013: expanding arguments
+      006.558  000.126: init highlight
+      021.764  014.089  001.787: sourcing $VIMRUNTIME/defaults.vim
+      021.767  000.120: sourcing vimrc file(s)
+      022.609  000.128  000.128: sourcing /usr/share/vim/vim90/plugin/gzip.vim
+      026.524  000.988: loading plugins
+      026.595  000.071: loading packages
+      026.642  000.047: reading viminfo
+      027.003  000.361: opening buffers
+      027.500  000.497: VimEnter autocommands
+    ";
+
+    let durations = Phase::durations(&StartupLog::parse(&dedent(content))?);
+
+    let cases = vec![
+      (Phase::Other, 0.007),
+      (Phase::ArgumentParsing, 0.793),
+      (Phase::InitHighlight, 5.758),
+      (Phase::SourcingVimrc, 15.209),
+      (Phase::LoadingPlugins, 4.828),
+      (Phase::ReadingViminfo, 0.047),
+      (Phase::OpeningBuffers, 0.361),
+      (Phase::VimEnter, 0.497),
+    ];
+
+    assert_eq!(durations.len(), cases.len());
+
+    for (phase, duration) in cases {
+      assert!(approx_eq!(f64, durations[&phase], duration, epsilon = 1e-9));
+    }
+
+    Ok(())
+  }
+
+  #[test]
+  fn from_description() {
+    let cases = vec![
+      ("parsing arguments", Phase::ArgumentParsing),
+      ("loading rtp plugins", Phase::LoadingPlugins),
+      ("reading ShaDa", Phase::ReadingViminfo),
+      ("done waiting for UI", Phase::UiWait),
+      ("--- NVIM STARTED ---", Phase::Other),
+    ];
+
+    for (description, phase) in cases {
+      assert_eq!(Phase::from_description(description), phase);
+    }
+  }
+}
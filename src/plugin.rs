@@ -3,11 +3,28 @@ use super::*;
 
 #[derive(Debug, Clone)]
 pub(crate) struct Plugin {
+  /// The longest the plugin may take according to the budget file,
+  /// tolerance included.
+  pub budget: Option<f64>,
+  /// The number of times discarded as outliers.
+  pub discarded: usize,
   pub name: String,
+  /// The directory of the plugin, or the script itself for system scripts.
+  pub path: Option<PathBuf>,
   pub times: Vec<f64>,
 }
 
 impl Plugin {
+  /// The number of resamples drawn to bootstrap a confidence interval.
+  const BOOTSTRAP_RESAMPLES: usize = 2000;
+
+  /// The confidence level of the confidence intervals of the mean.
+  const CONFIDENCE: f64 = 0.95;
+
+  /// The proportion of times dropped or clamped at each end by the trimmed
+  /// and winsorized means.
+  const TRIM: f64 = 0.2;
+
   /// Compute the average plugin start time.
   pub fn average(&self) -> f64 {
     let len = self.times.len();
@@ -15,6 +32,42 @@ impl Plugin {
       / f64::from(u32::try_from(len).unwrap_or(u32::MAX))
   }
 
+  /// Compute a confidence interval of the mean plugin start time by
+  /// resampling the times with replacement.
+  ///
+  /// The resampling is seeded, so the same times always give the same
+  /// interval.
+  pub fn bootstrap_interval(&self) -> (f64, f64) {
+    if self.times.len() < 2 {
+      return (self.average(), self.average());
+    }
+
+    let mut rng = Rng::new(
+      self
+        .times
+        .iter()
+        .fold(0, |seed, time| seed ^ time.to_bits().rotate_left(17)),
+    );
+
+    let len = self.times.len().to_f64().unwrap_or_default();
+
+    let means = (0..Self::BOOTSTRAP_RESAMPLES)
+      .map(|_| {
+        (0..self.times.len())
+          .map(|_| self.times[rng.index(self.times.len())])
+          .sum::<f64>()
+          / len
+      })
+      .collect::<Vec<f64>>();
+
+    let alpha = 1.0 - Self::CONFIDENCE;
+
+    (
+      quantile(&means, alpha / 2.0),
+      quantile(&means, 1.0 - alpha / 2.0),
+    )
+  }
+
   /// Compute the standard deviation among all plugin start times.
   pub fn deviation(&self) -> f64 {
     let avg = self.average();
@@ -33,13 +86,33 @@ impl Plugin {
     variance.sqrt()
   }
 
+  /// Compute the median absolute deviation of the plugin start times from
+  /// their median.
+  pub fn mad(&self) -> f64 {
+    let median = self.median();
+
+    Self::new(
+      self.name.clone(),
+      self
+        .times
+        .iter()
+        .map(|time| (time - median).abs())
+        .collect(),
+    )
+    .median()
+  }
+
   /// Compute the longest plugin start time
   pub fn max(&self) -> f64 {
     self.times.iter().copied().fold(f64::NAN, f64::max)
   }
 
-  /// Compute the median plugin start time.
+  /// Compute the median plugin start time, `NaN` without any time.
   pub fn median(&self) -> f64 {
+    if self.times.is_empty() {
+      return f64::NAN;
+    }
+
     let mut values = self.times.clone();
 
     values.sort_by(|a, b| a.partial_cmp(b).unwrap());
@@ -58,7 +131,93 @@ impl Plugin {
   }
 
   pub fn new(name: String, times: Vec<f64>) -> Self {
-    Self { name, times }
+    Self {
+      budget: None,
+      discarded: 0,
+      name,
+      path: None,
+      times,
+    }
+  }
+
+  /// Whether the plugin took longer than its budget according to an
+  /// estimator.
+  pub fn over_budget(&self, estimator: Estimator) -> bool {
+    self
+      .budget
+      .is_some_and(|budget| estimator.estimate(self) > budget)
+  }
+
+  /// Compute the `p`th percentile of the plugin start times, e.g 95 for
+  /// the time 95% of the iterations were faster than.
+  pub fn percentile(&self, p: f64) -> f64 {
+    quantile(&self.times, p / 100.0)
+  }
+
+  /// Compute a confidence interval of the mean plugin start time using
+  /// Student's t distribution.
+  pub fn t_interval(&self) -> (f64, f64) {
+    let (mean, len) = (self.average(), self.times.len());
+
+    if len < 2 {
+      return (mean, mean);
+    }
+
+    let n = len.to_f64().unwrap_or_default();
+
+    let variance = self
+      .times
+      .iter()
+      .map(|time| (time - mean).powi(2))
+      .sum::<f64>()
+      / (n - 1.0);
+
+    let margin = t_quantile(1.0 - (1.0 - Self::CONFIDENCE) / 2.0, n - 1.0)
+      * (variance / n).sqrt();
+
+    (mean - margin, mean + margin)
+  }
+
+  /// The sorted times along with how many of them are trimmed at each end.
+  fn trim(&self) -> (Vec<f64>, usize) {
+    let mut values = self.times.clone();
+
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let trim = (values.len().to_f64().unwrap_or_default() * Self::TRIM)
+      .floor()
+      .to_usize()
+      .unwrap_or_default();
+
+    (values, trim)
+  }
+
+  /// Compute the mean plugin start time after dropping the lowest and
+  /// highest 20% of the times.
+  pub fn trimmed_mean(&self) -> f64 {
+    let (values, trim) = self.trim();
+
+    Self::new(
+      self.name.clone(),
+      values[trim..values.len() - trim].to_vec(),
+    )
+    .average()
+  }
+
+  /// Compute the mean plugin start time after clamping the lowest and
+  /// highest 20% of the times to the closest remaining ones.
+  pub fn winsorized_mean(&self) -> f64 {
+    let (values, trim) = self.trim();
+
+    let len = values.len();
+
+    Self::new(
+      self.name.clone(),
+      (0..len)
+        .map(|i| values[i.clamp(trim, len.saturating_sub(trim + 1))])
+        .collect(),
+    )
+    .average()
   }
 }
 
@@ -136,7 +295,10 @@ mod tests {
       plugins.insert(
         a.clone(),
         Plugin {
+          budget: None,
+          discarded: 0,
           name: a.clone(),
+          path: None,
           times: b.clone(),
         },
       );
@@ -214,4 +376,83 @@ mod tests {
       ));
     }
   }
+
+  #[test]
+  fn robust() {
+    let plugin = Plugin::new(
+      "vim-just".into(),
+      vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 100.0],
+    );
+
+    assert!(approx_eq!(f64, plugin.trimmed_mean(), 5.5, ulps = 2));
+    assert!(approx_eq!(f64, plugin.winsorized_mean(), 5.5, ulps = 2));
+    assert!(approx_eq!(f64, plugin.mad(), 2.5, ulps = 2));
+
+    let plugin = Plugin::new("vim-just".into(), vec![1.0, 2.0, 3.0, 4.0, 50.0]);
+
+    assert!(approx_eq!(f64, plugin.trimmed_mean(), 3.0, ulps = 2));
+    assert!(approx_eq!(f64, plugin.winsorized_mean(), 3.0, ulps = 2));
+    assert!(approx_eq!(f64, plugin.mad(), 1.0, ulps = 2));
+  }
+
+  #[test]
+  fn percentile() {
+    let plugin =
+      Plugin::new("vim-just".into(), (1..=10).map(f64::from).collect());
+
+    let cases = vec![(50.0, 5.5), (90.0, 9.1), (95.0, 9.55), (99.0, 9.91)];
+
+    for (p, expected) in cases {
+      assert!(approx_eq!(
+        f64,
+        plugin.percentile(p),
+        expected,
+        epsilon = 1e-9
+      ));
+    }
+  }
+
+  #[test]
+  fn empty() {
+    let mut plugin = Plugin::new("vim-just".into(), Vec::new());
+
+    assert!(plugin.median().is_nan());
+    assert!(plugin.mad().is_nan());
+
+    for estimator in [
+      Estimator::Mean,
+      Estimator::Median,
+      Estimator::Trimmed,
+      Estimator::Winsorized,
+    ] {
+      assert!(estimator.estimate(&plugin).is_nan());
+    }
+
+    for outliers in [Outliers::Iqr, Outliers::Mad] {
+      outliers.reject(&mut plugin);
+      assert_eq!(plugin.discarded, 0);
+    }
+  }
+
+  #[test]
+  fn intervals() {
+    let plugin =
+      Plugin::new("vim-just".into(), vec![10.0, 12.0, 11.0, 13.0, 9.0]);
+
+    let (low, high) = plugin.t_interval();
+
+    assert!(approx_eq!(f64, low, 9.036_757, epsilon = 1e-5));
+    assert!(approx_eq!(f64, high, 12.963_243, epsilon = 1e-5));
+
+    let (low, high) = plugin.bootstrap_interval();
+
+    assert!((9.0..11.0).contains(&low));
+    assert!((11.0..=13.0).contains(&high) && high > 11.0);
+    assert_eq!(plugin.bootstrap_interval(), (low, high));
+
+    let plugin = Plugin::new("vim-just".into(), vec![4.0]);
+
+    assert_eq!(plugin.t_interval(), (4.0, 4.0));
+    assert_eq!(plugin.bootstrap_interval(), (4.0, 4.0));
+  }
 }
@@ -2,9 +2,9 @@ use super::*;
 
 pub(crate) trait Plugins {
   fn len_largest(&self) -> usize;
-  fn max(&self) -> f64;
-  fn min(&self) -> f64;
-  fn sort(&mut self, reversed: bool) -> Vec<Plugin>;
+  fn max(&self, estimator: Estimator) -> f64;
+  fn min(&self, estimator: Estimator) -> f64;
+  fn sort(&mut self, reversed: bool, estimator: Estimator) -> Vec<Plugin>;
 }
 
 impl Plugins for Vec<Plugin> {
@@ -17,30 +17,30 @@ impl Plugins for Vec<Plugin> {
       .len()
   }
 
-  fn max(&self) -> f64 {
+  fn max(&self, estimator: Estimator) -> f64 {
     self
       .iter()
-      .max_by(|a, b| a.average().partial_cmp(&b.average()).unwrap())
-      .unwrap()
-      .average()
+      .map(|plugin| estimator.estimate(plugin))
+      .fold(f64::NAN, f64::max)
   }
 
-  fn min(&self) -> f64 {
+  fn min(&self, estimator: Estimator) -> f64 {
     self
       .iter()
-      .min_by(|a, b| a.average().partial_cmp(&b.average()).unwrap())
-      .unwrap()
-      .average()
+      .map(|plugin| estimator.estimate(plugin))
+      .fold(f64::NAN, f64::min)
   }
 
-  fn sort(&mut self, reversed: bool) -> Vec<Plugin> {
+  fn sort(&mut self, reversed: bool, estimator: Estimator) -> Vec<Plugin> {
     let sorted = self;
 
     sorted.sort_by(|a, b| {
+      let (a, b) = (estimator.estimate(a), estimator.estimate(b));
+
       if reversed {
-        a.average().partial_cmp(&b.average()).unwrap()
+        a.partial_cmp(&b).unwrap()
       } else {
-        b.average().partial_cmp(&a.average()).unwrap()
+        b.partial_cmp(&a).unwrap()
       }
     });
 
@@ -60,7 +60,10 @@ mod tests {
     ]
     .iter()
     .map(|(a, b)| Plugin {
+      budget: None,
+      discarded: 0,
       name: a.clone(),
+      path: None,
       times: b.clone(),
     })
     .collect()
@@ -76,7 +79,7 @@ mod tests {
       String::from("vim-just"),
     ];
 
-    for (i, plugin) in plugins.sort(false).iter().enumerate() {
+    for (i, plugin) in plugins.sort(false, Estimator::Mean).iter().enumerate() {
       assert_eq!(plugin.name, order[i]);
     }
   }
@@ -91,7 +94,23 @@ mod tests {
       String::from("vim-prettier"),
     ];
 
-    for (i, plugin) in plugins.sort(true).iter().enumerate() {
+    for (i, plugin) in plugins.sort(true, Estimator::Mean).iter().enumerate() {
+      assert_eq!(plugin.name, order[i]);
+    }
+  }
+
+  #[test]
+  fn sort_median() {
+    let mut plugins = setup();
+
+    let order = [
+      String::from("vim-rooter"),
+      String::from("vim-prettier"),
+      String::from("vim-just"),
+    ];
+
+    for (i, plugin) in plugins.sort(false, Estimator::Median).iter().enumerate()
+    {
       assert_eq!(plugin.name, order[i]);
     }
   }
@@ -99,13 +118,18 @@ mod tests {
   #[test]
   fn max() {
     let plugins = setup();
-    assert!(approx_eq!(f64, plugins.max(), 8.0, ulps = 2));
+    assert!(approx_eq!(f64, plugins.max(Estimator::Mean), 8.0, ulps = 2));
   }
 
   #[test]
   fn min() {
     let plugins = setup();
-    assert!(approx_eq!(f64, plugins.min(), 4.75, ulps = 2));
+    assert!(approx_eq!(
+      f64,
+      plugins.min(Estimator::Mean),
+      4.75,
+      ulps = 2
+    ));
   }
 
   #[test]
@@ -3,39 +3,503 @@ use super::*;
 #[derive(Debug)]
 pub(crate) struct Printer {
   count: Option<usize>,
+  estimator: Estimator,
+  interval: Interval,
   prec: Option<usize>,
   reverse: bool,
 }
 
 impl Printer {
-  pub fn new(reverse: bool, count: Option<usize>, prec: Option<usize>) -> Self {
+  /// The significance level below which a change is flagged.
+  const SIGNIFICANCE: f64 = 0.05;
+
+  /// Print the marginal cost of each plugin next to the time attributed to
+  /// it in the log, the most expensive plugins first.
+  pub fn ablation(&self, ablation: &Ablation) {
+    let mut removals = ablation.removals.clone();
+
+    removals.sort_by(|a, b| {
+      a.delta(self.estimator)
+        .partial_cmp(&b.delta(self.estimator))
+        .unwrap()
+    });
+
+    if self.reverse {
+      removals.reverse();
+    }
+
+    removals.truncate(self.count.unwrap_or(removals.len()));
+
+    let rows = iter::once(vec![
+      String::from("Plugin"),
+      String::from("Attributed"),
+      String::from("Marginal"),
+      String::from("p-value"),
+    ])
+    .chain(removals.iter().map(|removal| {
+      vec![
+        removal.name.clone(),
+        self.cell(
+          ablation
+            .baseline
+            .plugins
+            .iter()
+            .find(|plugin| plugin.name == removal.name),
+        ),
+        self.time(-removal.delta(self.estimator)),
+        Self::p_value(removal.p_value),
+      ]
+    }))
+    .collect::<Vec<_>>();
+
+    if let Some(total) = &ablation.baseline.total {
+      println!("Total startup time: {} ms", self.estimate(total));
+    }
+
+    Self::table(
+      &format!(
+        "Marginal cost of {} plugins ({}).",
+        removals.len(),
+        ablation.test
+      ),
+      &rows,
+    );
+  }
+
+  /// The estimate of a plugin in a table, or `-` when it's missing.
+  fn cell(&self, plugin: Option<&Plugin>) -> String {
+    plugin.map_or_else(
+      || String::from("-"),
+      |plugin| self.time(self.estimator.estimate(plugin)),
+    )
+  }
+
+  /// Print the changes in startup time between two snapshots, the plugins
+  /// that got the slowest first.
+  pub fn compare(&self, before: &Path, after: &Path, comparison: &Comparison) {
+    let mut plugins = comparison.plugins.clone();
+
+    plugins.sort_by(|a, b| {
+      b.delta(self.estimator)
+        .partial_cmp(&a.delta(self.estimator))
+        .unwrap()
+    });
+
+    if self.reverse {
+      plugins.reverse();
+    }
+
+    plugins.truncate(self.count.unwrap_or(plugins.len()));
+
+    let rows = iter::once(vec![
+      String::from("Plugin"),
+      String::from("Before"),
+      String::from("After"),
+      String::from("Delta"),
+      String::from("p-value"),
+    ])
+    .chain(comparison.total.iter().chain(&plugins).map(|change| {
+      vec![
+        change.name.clone(),
+        self.cell(change.before.as_ref()),
+        self.cell(change.after.as_ref()),
+        self.signed(change.delta(self.estimator)),
+        match (&change.before, &change.after, change.p_value) {
+          (None, _, _) => String::from("added"),
+          (_, None, _) => String::from("removed"),
+          (_, _, p_value) => Self::p_value(p_value),
+        },
+      ]
+    }))
+    .collect::<Vec<_>>();
+
+    Self::table(
+      &format!(
+        "Comparing {} with {} ({}).",
+        before.display(),
+        after.display(),
+        comparison.test
+      ),
+      &rows,
+    );
+  }
+
+  /// The estimate of a plugin, followed by the half width of the
+  /// confidence interval of the mean when the estimator is the mean, e.g
+  /// `10.83 ± 0.42`.
+  fn estimate(&self, plugin: &Plugin) -> String {
+    let prec = self.prec.unwrap_or(2_usize);
+
+    let estimate = self.estimator.estimate(plugin);
+
+    if self.estimator != Estimator::Mean || plugin.times.len() < 2 {
+      return format!("{estimate:.prec$}");
+    }
+
+    let (low, high) = self.interval.compute(plugin);
+
+    format!("{estimate:.prec$} ± {:.prec$}", (high - low) / 2.0)
+  }
+
+  pub fn explain(&self, plugin: &str, files: &[Plugin]) {
+    let mut files = files.to_owned().sort(self.reverse, self.estimator);
+
+    files.truncate(self.count.unwrap_or(files.len()));
+
+    let rows = iter::once(vec![
+      String::from("File"),
+      String::from("Min"),
+      String::from("Median"),
+      String::from("Mean"),
+    ])
+    .chain(files.iter().map(|file| {
+      vec![
+        file.name.clone(),
+        self.time(file.min()),
+        self.time(file.median()),
+        self.time(file.average()),
+      ]
+    }))
+    .collect::<Vec<_>>();
+
+    Self::table(&format!("Files sourced by {plugin}."), &rows);
+  }
+
+  /// Print the total startup time and the times of the slowest plugins of
+  /// the latest run across every recorded run, oldest first.
+  pub fn history(&self, recordings: &[Recording]) {
+    let names = recordings
+      .last()
+      .map(|recording| {
+        recording.slowest(self.estimator, self.count.unwrap_or(5_usize))
+      })
+      .unwrap_or_default();
+
+    let rows = iter::once(
+      vec![String::from("Date"), String::from("Total")]
+        .into_iter()
+        .chain(names.iter().cloned())
+        .collect::<Vec<_>>(),
+    )
+    .chain(recordings.iter().map(|recording| {
+      vec![
+        date(recording.timestamp),
+        self.cell(recording.total().as_ref()),
+      ]
+      .into_iter()
+      .chain(
+        names
+          .iter()
+          .map(|name| self.cell(recording.plugin(name).as_ref())),
+      )
+      .collect()
+    }))
+    .collect::<Vec<Vec<String>>>();
+
+    Self::table(
+      &format!(
+        "Startup times of {} recorded run{}.",
+        recordings.len(),
+        if recordings.len() == 1 { "" } else { "s" }
+      ),
+      &rows,
+    );
+  }
+
+  /// Print the standalone cost of each plugin next to the time attributed
+  /// to it with the whole config and on its own, the most expensive plugins
+  /// first.
+  pub fn isolation(&self, isolation: &Isolation) {
+    let mut plugins = isolation.plugins.clone();
+
+    plugins.sort_by(|a, b| {
+      b.cost
+        .delta(self.estimator)
+        .partial_cmp(&a.cost.delta(self.estimator))
+        .unwrap()
+    });
+
+    if self.reverse {
+      plugins.reverse();
+    }
+
+    plugins.truncate(self.count.unwrap_or(plugins.len()));
+
+    let rows = iter::once(vec![
+      String::from("Plugin"),
+      String::from("In config"),
+      String::from("Isolated"),
+      String::from("Standalone"),
+      String::from("p-value"),
+    ])
+    .chain(plugins.iter().map(|standalone| {
+      vec![
+        standalone.cost.name.clone(),
+        self.cell(
+          isolation
+            .config
+            .plugins
+            .iter()
+            .find(|plugin| plugin.name == standalone.cost.name),
+        ),
+        self.cell(standalone.attributed.as_ref()),
+        self.time(standalone.cost.delta(self.estimator)),
+        Self::p_value(standalone.cost.p_value),
+      ]
+    }))
+    .collect::<Vec<_>>();
+
+    if let Some(total) = &isolation.config.total {
+      println!("Total startup time: {} ms", self.estimate(total));
+    }
+
+    Self::table(
+      &format!(
+        "Standalone cost of {} plugins ({}).",
+        plugins.len(),
+        isolation.test
+      ),
+      &rows,
+    );
+  }
+
+  pub fn new(
+    reverse: bool,
+    count: Option<usize>,
+    prec: Option<usize>,
+    estimator: Estimator,
+    interval: Interval,
+  ) -> Self {
     Self {
       count,
+      estimator,
+      interval,
       prec,
       reverse,
     }
   }
 
-  pub fn summary(&self, plugins: &[Plugin]) {
+  /// A marker for plugins and phases that took longer than their budget,
+  /// e.g ` (over budget of 5.00 ms)`.
+  fn over_budget(&self, plugin: &Plugin) -> String {
+    match plugin.budget {
+      Some(budget) if plugin.over_budget(self.estimator) => format!(
+        " (over budget of {:.1$} ms)",
+        budget,
+        self.prec.unwrap_or(2_usize)
+      ),
+      _ => String::new(),
+    }
+  }
+
+  /// The p-value of a change, marked with a `*` when it's significant at
+  /// the 5% level.
+  fn p_value(p_value: Option<f64>) -> String {
+    match p_value {
+      Some(p_value) if p_value < Self::SIGNIFICANCE => {
+        format!("{p_value:.3} *")
+      }
+      Some(p_value) => format!("{p_value:.3}"),
+      None => String::from("-"),
+    }
+  }
+
+  pub fn phases(&self, phases: &[Plugin]) {
+    if phases.is_empty() {
+      return;
+    }
+
+    let phases = phases.to_owned();
+
+    let header = String::from("Startup phases.");
+
+    println!("{header}");
+    println!("{}", repeat("=", header.len()));
+
+    for phase in &phases {
+      println!(
+        "{} {}{}",
+        format_args!("{:1$}", phase.name, &phases.len_largest()),
+        format_args!(
+          "{:.1$}",
+          self.estimator.estimate(phase),
+          self.prec.unwrap_or(2_usize)
+        ),
+        self.over_budget(phase)
+      );
+    }
+
+    println!("{}", repeat("=", header.len()));
+  }
+
+  /// A time with the output precision and an explicit sign, e.g `+1.50`,
+  /// see `time`.
+  fn signed(&self, time: f64) -> String {
+    let time = self.time(time);
+
+    if time.starts_with('-') || Self::zero(&time) {
+      time
+    } else {
+      format!("+{time}")
+    }
+  }
+
+  pub fn summary(
+    &self,
+    version: &str,
+    plugins: &[Plugin],
+    total: Option<&Plugin>,
+    warmup: Option<&Plugin>,
+  ) {
     let mut plugins = plugins.to_owned();
 
+    if !version.is_empty() {
+      println!("Editor: {version}");
+    }
+
+    if let Some(total) = total {
+      println!("Total startup time: {} ms", self.estimate(total));
+    }
+
+    if let Some(warmup) = warmup {
+      println!(
+        "Warmup startup time: {:.1$} ms (not included)",
+        self.estimator.estimate(warmup),
+        self.prec.unwrap_or(2_usize)
+      );
+    }
+
     plugins.truncate(self.count.unwrap_or(10_usize));
 
     let order = if self.reverse { "fastest" } else { "slowest" };
-    let header = format!("Top {} {order} (n)vim plugins.", plugins.len(),);
+    let header = format!("Top {} {order} (n)vim plugins.", plugins.len());
 
     println!("{header}");
     println!("{}", repeat("=", header.len()));
 
     for (i, plugin) in plugins.iter().enumerate() {
       println!(
-        "{} {} {}",
+        "{} {} {}{}{}",
         format_args!("{:<1$}", i + 1, plugins.len().to_string().len() + 2),
         format_args!("{:1$}", plugin.name, &plugins.len_largest()),
-        format_args!("{:.1$}", plugin.average(), self.prec.unwrap_or(2_usize))
+        self.estimate(plugin),
+        if plugin.discarded > 0 {
+          format!(" ({} discarded)", plugin.discarded)
+        } else {
+          String::new()
+        },
+        self.over_budget(plugin)
       );
     }
 
     println!("{}", repeat("=", header.len()));
   }
+
+  /// Print the rows of a table under a header, the first column left aligned
+  /// and the others right aligned. The `*` of a significant p-value is kept
+  /// out of the alignment, and explained under the table.
+  fn table(header: &str, rows: &[Vec<String>]) {
+    let rows = rows
+      .iter()
+      .map(|row| {
+        row
+          .iter()
+          .map(|cell| {
+            cell
+              .strip_suffix(" *")
+              .map_or((cell.as_str(), ""), |cell| (cell, " *"))
+          })
+          .collect::<Vec<_>>()
+      })
+      .collect::<Vec<_>>();
+
+    let widths = (0..rows.first().map_or(0, Vec::len))
+      .map(|i| {
+        rows
+          .iter()
+          .map(|row| row[i].0.len())
+          .max()
+          .unwrap_or_default()
+      })
+      .collect::<Vec<_>>();
+
+    println!("{header}");
+    println!("{}", repeat("=", header.len()));
+
+    for row in &rows {
+      let line = row
+        .iter()
+        .zip(&widths)
+        .enumerate()
+        .map(|(i, ((cell, marker), width))| {
+          if i == 0 {
+            format!("{cell:<width$}{marker}")
+          } else {
+            format!("{cell:>width$}{marker}")
+          }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+      println!("{}", line.trim_end());
+    }
+
+    println!("{}", repeat("=", header.len()));
+
+    if rows.iter().flatten().any(|(_, marker)| !marker.is_empty()) {
+      println!("* significant at the 5% level");
+    }
+  }
+
+  /// A time with the output precision, without the sign of a time that
+  /// rounds to zero, e.g `0.00` rather than `-0.00`.
+  fn time(&self, time: f64) -> String {
+    let time = format!("{:.1$}", time, self.prec.unwrap_or(2_usize));
+
+    if Self::zero(&time) {
+      time.trim_start_matches('-').to_owned()
+    } else {
+      time
+    }
+  }
+
+  /// Whether a formatted time rounds to zero, e.g `-0.00`.
+  fn zero(time: &str) -> bool {
+    time.chars().all(|c| matches!(c, '-' | '0' | '.'))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn time() {
+    let printer =
+      Printer::new(false, None, None, Estimator::Mean, Interval::default());
+
+    for (time, expected) in [
+      (-0.0, "0.00"),
+      (-0.001, "0.00"),
+      (-0.01, "-0.01"),
+      (1.5, "1.50"),
+    ] {
+      assert_eq!(printer.time(time), expected);
+    }
+  }
+
+  #[test]
+  fn signed() {
+    let printer =
+      Printer::new(false, None, None, Estimator::Mean, Interval::default());
+
+    for (time, expected) in [
+      (-0.001, "0.00"),
+      (0.001, "0.00"),
+      (-0.01, "-0.01"),
+      (0.07, "+0.07"),
+    ] {
+      assert_eq!(printer.signed(time), expected);
+    }
+  }
 }
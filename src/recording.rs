@@ -0,0 +1,44 @@
+use super::*;
+
+/// A run stored in the history, along with when it was recorded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Recording {
+  #[serde(flatten)]
+  pub document: Document,
+  /// The number of seconds since the unix epoch when the run finished.
+  pub timestamp: u64,
+}
+
+impl Recording {
+  /// The plugin with the given name, if it was loaded during the run.
+  pub fn plugin(&self, name: &str) -> Option<Plugin> {
+    self
+      .document
+      .plugins
+      .iter()
+      .find(|entry| entry.name == name)
+      .cloned()
+      .map(Plugin::from)
+  }
+
+  /// The names of the `count` slowest plugins of the run according to an
+  /// estimator.
+  pub fn slowest(&self, estimator: Estimator, count: usize) -> Vec<String> {
+    let mut plugins = self
+      .document
+      .plugins
+      .iter()
+      .cloned()
+      .map(Plugin::from)
+      .collect::<Vec<Plugin>>()
+      .sort(false, estimator);
+
+    plugins.truncate(count);
+
+    plugins.into_iter().map(|plugin| plugin.name).collect()
+  }
+
+  pub fn total(&self) -> Option<Plugin> {
+    self.document.total.clone().map(Plugin::from)
+  }
+}
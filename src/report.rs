@@ -0,0 +1,318 @@
+use super::*;
+
+/// The measurements of every iteration of a run, grouped by what they
+/// measure.
+#[derive(Debug, Clone)]
+pub(crate) struct Report {
+  /// The files sourced for each plugin, keyed by plugin name.
+  pub files: HashMap<String, Vec<Plugin>>,
+  pub phases: Vec<Plugin>,
+  pub plugins: Vec<Plugin>,
+  pub run: Run,
+  /// The total startup time of each iteration.
+  pub total: Option<Plugin>,
+  /// The sourcing tree of each iteration.
+  pub trees: Vec<Tree>,
+  /// The total startup time of each warmup iteration.
+  pub warmup: Option<Plugin>,
+}
+
+impl Report {
+  /// Warmup samples are only used for their total startup time, they're
+  /// left out of every other statistic.
+  pub fn new(samples: &[Sample], warmup: &[Sample], run: Run) -> Self {
+    let mut files = HashMap::new();
+    let mut paths = HashMap::new();
+    let mut phases = HashMap::new();
+    let mut plugins = HashMap::new();
+
+    for sample in samples {
+      for (plugin, times) in &sample.files {
+        for (k, v) in times {
+          files
+            .entry(plugin.to_owned())
+            .or_insert_with(HashMap::new)
+            .entry(k.to_owned())
+            .or_insert_with(Vec::new)
+            .push(*v);
+        }
+      }
+
+      for (k, v) in &sample.paths {
+        paths.entry(k.to_owned()).or_insert_with(|| v.clone());
+      }
+
+      for (k, v) in &sample.phases {
+        phases.entry(*k).or_insert_with(Vec::new).push(*v);
+      }
+
+      for (k, v) in &sample.plugins {
+        plugins
+          .entry(k.to_owned())
+          .or_insert_with(Vec::new)
+          .push(*v);
+      }
+    }
+
+    let mut report = Self {
+      files: files
+        .into_iter()
+        .map(|(plugin, times)| {
+          (
+            plugin,
+            times
+              .into_iter()
+              .map(|(k, v)| Plugin::new(k, v))
+              .collect::<Vec<Plugin>>(),
+          )
+        })
+        .collect(),
+      phases: Phase::ALL
+        .iter()
+        .filter_map(|phase| {
+          phases
+            .remove(phase)
+            .map(|times| Plugin::new(phase.to_string(), times))
+        })
+        .collect(),
+      plugins: plugins
+        .into_iter()
+        .map(|(k, v)| Plugin {
+          path: paths.get(&k).cloned(),
+          ..Plugin::new(k, v)
+        })
+        .collect(),
+      run,
+      total: Self::total("total", samples),
+      trees: samples.iter().map(|sample| sample.tree.clone()).collect(),
+      warmup: Self::total("warmup", warmup),
+    };
+
+    report.reject();
+
+    report
+  }
+
+  /// Discard outlying times according to the outlier rejection method of
+  /// the run. Warmup times are left as is.
+  fn reject(&mut self) {
+    let outliers = self.run.outliers;
+
+    for plugin in self
+      .files
+      .values_mut()
+      .flatten()
+      .chain(&mut self.phases)
+      .chain(&mut self.plugins)
+      .chain(&mut self.total)
+    {
+      outliers.reject(plugin);
+    }
+  }
+
+  /// The total startup time of each sample that has one.
+  fn total(name: &str, samples: &[Sample]) -> Option<Plugin> {
+    Some(
+      samples
+        .iter()
+        .filter_map(|sample| sample.total)
+        .collect::<Vec<f64>>(),
+    )
+    .filter(|times| !times.is_empty())
+    .map(|times| Plugin::new(name.to_owned(), times))
+  }
+}
+
+#[cfg(test)]
+impl Report {
+  /// A report of a single run with a total startup time and plugins that
+  /// took 1 ms each, along with their paths.
+  pub(crate) fn single(total: f64, plugins: &[(&str, Option<&str>)]) -> Self {
+    let mut report = Report::new(
+      &[Sample {
+        plugins: plugins
+          .iter()
+          .map(|(name, _)| ((*name).to_owned(), 1.0))
+          .collect(),
+        total: Some(total),
+        ..Sample::default()
+      }],
+      &[],
+      Run::default(),
+    );
+
+    for plugin in &mut report.plugins {
+      plugin.path = plugins
+        .iter()
+        .find(|(name, _)| *name == plugin.name)
+        .and_then(|(_, path)| path.map(PathBuf::from));
+    }
+
+    report
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn new() {
+    let samples = vec![
+      Sample {
+        files: HashMap::from([(
+          "vim-just".into(),
+          HashMap::from([("ftdetect/just.vim".into(), 1.0)]),
+        )]),
+        paths: HashMap::from([(
+          "vim-just".into(),
+          PathBuf::from("/Users/.vim/plugged/vim-just"),
+        )]),
+        phases: HashMap::from([
+          (Phase::VimEnter, 1.0),
+          (Phase::ArgumentParsing, 2.0),
+        ]),
+        plugins: HashMap::from([("vim-just".into(), 1.0)]),
+        total: Some(10.0),
+        tree: Tree::default(),
+      },
+      Sample {
+        files: HashMap::from([(
+          "vim-just".into(),
+          HashMap::from([
+            ("ftdetect/just.vim".into(), 1.5),
+            ("plugin/just.vim".into(), 0.5),
+          ]),
+        )]),
+        paths: HashMap::new(),
+        phases: HashMap::from([(Phase::VimEnter, 3.0)]),
+        plugins: HashMap::from([
+          ("vim-just".into(), 2.0),
+          ("vim-rooter".into(), 4.0),
+        ]),
+        total: None,
+        tree: Tree::default(),
+      },
+    ];
+
+    let report = Report::new(
+      &samples,
+      &[],
+      Run {
+        iterations: 2,
+        ..Run::default()
+      },
+    );
+
+    let mut files = report.files["vim-just"]
+      .iter()
+      .map(|file| (file.name.as_str(), file.times.clone()))
+      .collect::<Vec<_>>();
+
+    files.sort_by(|a, b| a.0.cmp(b.0));
+
+    assert_eq!(
+      files,
+      vec![
+        ("ftdetect/just.vim", vec![1.0, 1.5]),
+        ("plugin/just.vim", vec![0.5]),
+      ]
+    );
+
+    assert_eq!(
+      report
+        .phases
+        .iter()
+        .map(|phase| (phase.name.as_str(), phase.times.clone()))
+        .collect::<Vec<_>>(),
+      vec![
+        ("argument parsing", vec![2.0]),
+        ("VimEnter autocommands", vec![1.0, 3.0]),
+      ]
+    );
+
+    let mut plugins = report
+      .plugins
+      .iter()
+      .map(|plugin| (plugin.name.as_str(), plugin.times.clone()))
+      .collect::<Vec<_>>();
+
+    plugins.sort_by(|a, b| a.0.cmp(b.0));
+
+    assert_eq!(
+      report
+        .plugins
+        .iter()
+        .find(|plugin| plugin.name == "vim-just")
+        .and_then(|plugin| plugin.path.clone()),
+      Some(PathBuf::from("/Users/.vim/plugged/vim-just"))
+    );
+
+    assert_eq!(
+      plugins,
+      vec![("vim-just", vec![1.0, 2.0]), ("vim-rooter", vec![4.0])]
+    );
+
+    assert_eq!(report.total.map(|total| total.times), Some(vec![10.0]));
+    assert!(report.warmup.is_none());
+  }
+
+  #[test]
+  fn warmup() {
+    let sample = |plugin: f64, total: f64| Sample {
+      plugins: HashMap::from([("vim-just".into(), plugin)]),
+      total: Some(total),
+      ..Sample::default()
+    };
+
+    let report = Report::new(
+      &[sample(1.0, 10.0), sample(2.0, 12.0)],
+      &[sample(50.0, 200.0)],
+      Run {
+        iterations: 2,
+        warmup: 1,
+        ..Run::default()
+      },
+    );
+
+    assert_eq!(report.plugins[0].times, vec![1.0, 2.0]);
+
+    assert_eq!(
+      report.total.map(|total| total.times),
+      Some(vec![10.0, 12.0])
+    );
+
+    assert_eq!(
+      report.warmup.map(|warmup| (warmup.name, warmup.times)),
+      Some((String::from("warmup"), vec![200.0]))
+    );
+  }
+
+  #[test]
+  fn outliers() {
+    let samples = [1.0, 1.1, 0.9, 1.0, 9.0]
+      .iter()
+      .map(|time| Sample {
+        plugins: HashMap::from([("vim-just".into(), *time)]),
+        total: Some(time * 10.0),
+        ..Sample::default()
+      })
+      .collect::<Vec<_>>();
+
+    let report = Report::new(
+      &samples,
+      &samples[..1],
+      Run {
+        iterations: 5,
+        outliers: Outliers::Iqr,
+        warmup: 1,
+        ..Run::default()
+      },
+    );
+
+    assert_eq!(report.plugins[0].times, vec![1.0, 1.1, 0.9, 1.0]);
+    assert_eq!(report.plugins[0].discarded, 1);
+    assert_eq!(report.total.map(|total| total.discarded), Some(1));
+    assert_eq!(report.warmup.map(|warmup| warmup.discarded), Some(0));
+  }
+}
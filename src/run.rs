@@ -0,0 +1,34 @@
+use super::*;
+
+/// The parameters a report was produced with.
+#[derive(Debug, Clone)]
+pub(crate) struct Run {
+  pub command: Command,
+  pub estimator: Estimator,
+  pub file: Option<PathBuf>,
+  pub iterations: i64,
+  pub outliers: Outliers,
+  pub sys: bool,
+  pub timing: TimingMode,
+  /// The version of the editor, e.g `NVIM v0.10.0`.
+  pub version: String,
+  /// The number of iterations run before measuring.
+  pub warmup: i64,
+}
+
+#[cfg(test)]
+impl Default for Run {
+  fn default() -> Self {
+    Self {
+      command: Command::Vim,
+      estimator: Estimator::default(),
+      file: None,
+      iterations: 1,
+      outliers: Outliers::default(),
+      sys: false,
+      timing: TimingMode::default(),
+      version: String::new(),
+      warmup: 0,
+    }
+  }
+}
@@ -0,0 +1,163 @@
+use super::*;
+
+/// The directories the editor searches for runtime files and lua modules.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Runtimepath {
+  pub entries: Vec<PathBuf>,
+  /// The directory holding the runtime files shipped with the editor.
+  pub vimruntime: Option<PathBuf>,
+}
+
+impl Runtimepath {
+  /// Every directory inside of a plugin directory, in case the editor can't
+  /// be asked for its `runtimepath`.
+  pub fn plugins(directory: &Path) -> Self {
+    let mut entries = fs::read_dir(directory)
+      .map(|entries| {
+        entries
+          .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+          .filter(|path| path.is_dir())
+          .collect::<Vec<_>>()
+      })
+      .unwrap_or_default();
+
+    entries.sort();
+
+    Self {
+      entries,
+      vimruntime: None,
+    }
+  }
+
+  /// Ask neovim for its `runtimepath` once the configuration has been
+  /// loaded, which includes every plugin added by a plugin manager. The
+  /// editor is started with the same arguments as the measured run, since
+  /// they can change the `runtimepath`, e.g `--noplugin`.
+  pub fn query(
+    editor: &Editor,
+    file: Option<&Path>,
+    config: Option<&Path>,
+    arguments: &[String],
+    timeout: Duration,
+  ) -> Result<Self> {
+    let mut command = editor.command();
+
+    command
+      .stdin(Stdio::null())
+      .stderr(Stdio::null())
+      .args(config.iter().flat_map(|config| [Path::new("-u"), config]))
+      .args(arguments)
+      .args(file)
+      .arg("--headless")
+      .arg("-c")
+      .arg("lua io.stdout:write(vim.o.runtimepath)")
+      .arg("-c")
+      .arg("qa!");
+
+    let output = editor.output(command, timeout)?;
+
+    Ok(Self {
+      entries: String::from_utf8_lossy(&output)
+        .split(',')
+        .filter(|entry| !entry.is_empty())
+        .map(PathBuf::from)
+        .collect(),
+      vimruntime: None,
+    })
+  }
+
+  /// Find the entry that provides a lua module, along with the path of the
+  /// module relative to the entry.
+  ///
+  /// Like `require()`, the entries are searched in order for either
+  /// `lua/foo/bar.lua` or `lua/foo/bar/init.lua` when resolving `foo.bar`.
+  pub fn resolve(&self, module: &str) -> Option<(&Path, String)> {
+    let base = format!("lua/{}", module.replace('.', "/"));
+
+    self.entries.iter().find_map(|entry| {
+      vec![format!("{base}.lua"), format!("{base}/init.lua")]
+        .into_iter()
+        .find(|file| entry.join(file).is_file())
+        .map(|file| (entry.as_path(), file))
+    })
+  }
+
+  /// Ask the editor for `$VIMRUNTIME`, which is where its runtime files live
+  /// unless it was overridden, e.g `/usr/share/vim/vim91` or
+  /// `/opt/homebrew/share/nvim/runtime`. The arguments of the measured run
+  /// are passed along, since they can override it too.
+  pub fn vimruntime(
+    editor: &Editor,
+    arguments: &[String],
+    timeout: Duration,
+  ) -> Result<Option<PathBuf>> {
+    let mut command = editor.command();
+
+    command
+      .stdin(Stdio::null())
+      .stderr(Stdio::null())
+      .args(arguments)
+      .arg("-es")
+      .arg("-c")
+      .arg("call writefile([$VIMRUNTIME], '/dev/stdout')")
+      .arg("-c")
+      .arg("qa!");
+
+    let output = editor.output(command, timeout)?;
+
+    Ok(
+      Some(String::from_utf8_lossy(&output).trim().replace('\\', "/"))
+        .filter(|vimruntime| !vimruntime.is_empty())
+        .map(PathBuf::from),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn resolve() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+
+    let (telescope, plenary) = (
+      tempdir.path().join("telescope.nvim"),
+      tempdir.path().join("plenary.nvim"),
+    );
+
+    fs::create_dir_all(telescope.join("lua/telescope/builtin"))?;
+    fs::write(telescope.join("lua/telescope/init.lua"), "")?;
+    fs::write(telescope.join("lua/telescope/builtin/init.lua"), "")?;
+
+    fs::create_dir_all(plenary.join("lua/plenary"))?;
+    fs::write(plenary.join("lua/plenary/async.lua"), "")?;
+
+    let runtimepath = Runtimepath {
+      entries: vec![telescope.clone(), plenary.clone()],
+      vimruntime: None,
+    };
+
+    assert_eq!(
+      runtimepath.resolve("telescope"),
+      Some((telescope.as_path(), String::from("lua/telescope/init.lua")))
+    );
+
+    assert_eq!(
+      runtimepath.resolve("telescope.builtin"),
+      Some((
+        telescope.as_path(),
+        String::from("lua/telescope/builtin/init.lua")
+      ))
+    );
+
+    assert_eq!(
+      runtimepath.resolve("plenary.async"),
+      Some((plenary.as_path(), String::from("lua/plenary/async.lua")))
+    );
+
+    assert_eq!(runtimepath.resolve("vim.lsp"), None);
+
+    Ok(())
+  }
+}
@@ -0,0 +1,17 @@
+use super::*;
+
+/// The measurements taken from a single editor startup.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Sample {
+  /// The time spent in each file of a plugin, keyed by plugin name and
+  /// then by the path of the file relative to the plugin.
+  pub files: HashMap<String, HashMap<String, f64>>,
+  /// The directory of each plugin, or the script itself for system
+  /// scripts, keyed by plugin name.
+  pub paths: HashMap<String, PathBuf>,
+  pub phases: HashMap<Phase, f64>,
+  pub plugins: HashMap<String, f64>,
+  /// The clock at which startup finished.
+  pub total: Option<f64>,
+  pub tree: Tree,
+}
@@ -0,0 +1,362 @@
+use super::*;
+
+/// The version of the JSON output, bumped whenever a field is removed or
+/// changes meaning. New fields may be added without bumping it.
+pub(crate) const SCHEMA_VERSION: u32 = 1;
+
+/// The JSON representation of a report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Document {
+  pub phases: Vec<Entry>,
+  pub plugins: Vec<Entry>,
+  pub run: RunInfo,
+  pub schema_version: u32,
+  /// The total startup time, measured up to the `--- VIM STARTED ---` event.
+  #[serde(default)]
+  pub total: Option<Entry>,
+  /// The total startup time of the warmup iterations, which are left out of
+  /// every other statistic.
+  #[serde(default)]
+  pub warmup: Option<Entry>,
+}
+
+impl From<&Report> for Document {
+  fn from(report: &Report) -> Self {
+    Self {
+      phases: report.phases.iter().map(Entry::from).collect(),
+      plugins: report.plugins.iter().map(Entry::from).collect(),
+      run: RunInfo::from(&report.run),
+      schema_version: SCHEMA_VERSION,
+      total: report.total.as_ref().map(Entry::from),
+      warmup: report.warmup.as_ref().map(Entry::from),
+    }
+  }
+}
+
+/// A plugin or phase along with every sample taken of it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Entry {
+  /// The longest the entry may take according to the budget file, tolerance
+  /// included.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub budget: Option<f64>,
+  /// The number of times discarded as outliers, which are left out of
+  /// `times`.
+  #[serde(default)]
+  pub discarded: usize,
+  pub name: String,
+  /// The directory of the plugin, or the script itself for system scripts.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub path: Option<PathBuf>,
+  pub statistics: Statistics,
+  /// The time in milliseconds of each iteration.
+  pub times: Vec<f64>,
+}
+
+impl From<&Plugin> for Entry {
+  fn from(plugin: &Plugin) -> Self {
+    Self {
+      budget: plugin.budget,
+      discarded: plugin.discarded,
+      name: plugin.name.clone(),
+      path: plugin.path.clone(),
+      statistics: Statistics {
+        average: plugin.average(),
+        bootstrap_interval: plugin.bootstrap_interval(),
+        deviation: plugin.deviation(),
+        mad: plugin.mad(),
+        max: plugin.max(),
+        median: plugin.median(),
+        min: plugin.min(),
+        p90: plugin.percentile(90.0),
+        p95: plugin.percentile(95.0),
+        p99: plugin.percentile(99.0),
+        t_interval: plugin.t_interval(),
+        trimmed_mean: plugin.trimmed_mean(),
+        winsorized_mean: plugin.winsorized_mean(),
+      },
+      times: plugin.times.clone(),
+    }
+  }
+}
+
+impl From<Entry> for Plugin {
+  fn from(entry: Entry) -> Self {
+    Self {
+      budget: entry.budget,
+      discarded: entry.discarded,
+      name: entry.name,
+      path: entry.path,
+      times: entry.times,
+    }
+  }
+}
+
+/// A single line of the NDJSON output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub(crate) enum Record {
+  Phase(Entry),
+  Plugin(Entry),
+  Run {
+    #[serde(flatten)]
+    run: RunInfo,
+    schema_version: u32,
+  },
+  Total(Entry),
+  Warmup(Entry),
+}
+
+impl Record {
+  /// The records of a report, starting with the run.
+  pub fn all(report: &Report) -> Vec<Record> {
+    iter::once(Record::Run {
+      run: RunInfo::from(&report.run),
+      schema_version: SCHEMA_VERSION,
+    })
+    .chain(
+      report
+        .plugins
+        .iter()
+        .map(|p| Record::Plugin(Entry::from(p))),
+    )
+    .chain(report.phases.iter().map(|p| Record::Phase(Entry::from(p))))
+    .chain(report.total.iter().map(|p| Record::Total(Entry::from(p))))
+    .chain(report.warmup.iter().map(|p| Record::Warmup(Entry::from(p))))
+    .collect()
+  }
+}
+
+/// The JSON representation of the parameters of a run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct RunInfo {
+  pub command: String,
+  #[serde(default)]
+  pub estimator: String,
+  pub file: Option<PathBuf>,
+  pub iterations: i64,
+  #[serde(default)]
+  pub outliers: String,
+  pub sys: bool,
+  pub timing: String,
+  #[serde(default)]
+  pub version: String,
+  #[serde(default)]
+  pub warmup: i64,
+}
+
+impl From<&Run> for RunInfo {
+  fn from(run: &Run) -> Self {
+    Self {
+      command: run.command.to_string(),
+      estimator: run.estimator.to_string(),
+      file: run.file.clone(),
+      iterations: run.iterations,
+      outliers: run.outliers.to_string(),
+      sys: run.sys,
+      timing: run.timing.to_string(),
+      version: run.version.clone(),
+      warmup: run.warmup,
+    }
+  }
+}
+
+/// The statistics of an entry, in milliseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Statistics {
+  pub average: f64,
+  /// The 95% confidence interval of the mean, bootstrapped from the times.
+  #[serde(default)]
+  pub bootstrap_interval: (f64, f64),
+  pub deviation: f64,
+  /// The median absolute deviation from the median.
+  #[serde(default)]
+  pub mad: f64,
+  pub max: f64,
+  pub median: f64,
+  pub min: f64,
+  #[serde(default)]
+  pub p90: f64,
+  #[serde(default)]
+  pub p95: f64,
+  #[serde(default)]
+  pub p99: f64,
+  /// The 95% confidence interval of the mean, using Student's t
+  /// distribution.
+  #[serde(default)]
+  pub t_interval: (f64, f64),
+  #[serde(default)]
+  pub trimmed_mean: f64,
+  #[serde(default)]
+  pub winsorized_mean: f64,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn report() -> Report {
+    let mut report = Report::new(
+      &[Sample {
+        phases: HashMap::from([(Phase::LoadingPlugins, 3.0)]),
+        plugins: HashMap::from([("vim-just".into(), 1.5)]),
+        total: Some(20.0),
+        ..Sample::default()
+      }],
+      &[Sample {
+        total: Some(80.0),
+        ..Sample::default()
+      }],
+      Run {
+        command: Command::Neovim,
+        version: String::from("NVIM v0.10.0"),
+        warmup: 1,
+        ..Run::default()
+      },
+    );
+
+    report.plugins[0].times.push(2.5);
+
+    report
+  }
+
+  #[test]
+  fn document() -> Result<()> {
+    let document = Document::from(&report());
+
+    let mut value = serde_json::to_value(&document)?;
+
+    let (low, high) = document.plugins[0].statistics.t_interval;
+
+    assert!(approx_eq!(f64, low, -4.353_102_368_086_784, epsilon = 1e-9));
+    assert!(approx_eq!(f64, high, 8.353_102_368_086_784, epsilon = 1e-9));
+
+    value["plugins"][0]["statistics"]["t_interval"] = json!(null);
+
+    assert_eq!(
+      value,
+      json!({
+        "schema_version": 1,
+        "run": {
+          "command": "nvim",
+          "estimator": "mean",
+          "file": null,
+          "iterations": 1,
+          "outliers": "none",
+          "sys": false,
+          "timing": "self",
+          "version": "NVIM v0.10.0",
+          "warmup": 1,
+        },
+        "plugins": [{
+          "discarded": 0,
+          "name": "vim-just",
+          "times": [1.5, 2.5],
+          "statistics": {
+            "average": 2.0,
+            "bootstrap_interval": [1.5, 2.5],
+            "deviation": 0.5,
+            "mad": 0.5,
+            "max": 2.5,
+            "median": 2.0,
+            "min": 1.5,
+            "p90": 2.4,
+            "p95": 2.45,
+            "p99": 2.49,
+            "t_interval": null,
+            "trimmed_mean": 2.0,
+            "winsorized_mean": 2.0,
+          },
+        }],
+        "phases": [{
+          "discarded": 0,
+          "name": "loading plugins",
+          "times": [3.0],
+          "statistics": {
+            "average": 3.0,
+            "bootstrap_interval": [3.0, 3.0],
+            "deviation": 0.0,
+            "mad": 0.0,
+            "max": 3.0,
+            "median": 3.0,
+            "min": 3.0,
+            "p90": 3.0,
+            "p95": 3.0,
+            "p99": 3.0,
+            "t_interval": [3.0, 3.0],
+            "trimmed_mean": 3.0,
+            "winsorized_mean": 3.0,
+          },
+        }],
+        "total": {
+          "discarded": 0,
+          "name": "total",
+          "times": [20.0],
+          "statistics": {
+            "average": 20.0,
+            "bootstrap_interval": [20.0, 20.0],
+            "deviation": 0.0,
+            "mad": 0.0,
+            "max": 20.0,
+            "median": 20.0,
+            "min": 20.0,
+            "p90": 20.0,
+            "p95": 20.0,
+            "p99": 20.0,
+            "t_interval": [20.0, 20.0],
+            "trimmed_mean": 20.0,
+            "winsorized_mean": 20.0,
+          },
+        },
+        "warmup": {
+          "discarded": 0,
+          "name": "warmup",
+          "times": [80.0],
+          "statistics": {
+            "average": 80.0,
+            "bootstrap_interval": [80.0, 80.0],
+            "deviation": 0.0,
+            "mad": 0.0,
+            "max": 80.0,
+            "median": 80.0,
+            "min": 80.0,
+            "p90": 80.0,
+            "p95": 80.0,
+            "p99": 80.0,
+            "t_interval": [80.0, 80.0],
+            "trimmed_mean": 80.0,
+            "winsorized_mean": 80.0,
+          },
+        },
+      })
+    );
+
+    assert_eq!(
+      serde_json::from_value::<Document>(serde_json::to_value(&document)?)?,
+      document
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn records() -> Result<()> {
+    let records = Record::all(&report())
+      .iter()
+      .map(serde_json::to_value)
+      .collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(records.len(), 5);
+
+    assert_eq!(records[0]["type"], "run");
+    assert_eq!(records[0]["schema_version"], 1);
+    assert_eq!(records[0]["command"], "nvim");
+    assert_eq!(records[1]["type"], "plugin");
+    assert_eq!(records[1]["name"], "vim-just");
+    assert_eq!(records[2]["type"], "phase");
+    assert_eq!(records[3]["type"], "total");
+    assert_eq!(records[4]["type"], "warmup");
+
+    Ok(())
+  }
+}
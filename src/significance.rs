@@ -0,0 +1,51 @@
+use super::*;
+
+/// How the difference between the times of a plugin before and after a
+/// change is tested for significance.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) enum Significance {
+  /// The Mann-Whitney U test, which compares ranks rather than means and so
+  /// makes no assumption about how the times are distributed.
+  MannWhitney,
+  /// Welch's t-test, which compares means without assuming equal variances.
+  #[default]
+  Welch,
+}
+
+impl Display for Significance {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Significance::MannWhitney => write!(f, "mann-whitney"),
+      Significance::Welch => write!(f, "welch"),
+    }
+  }
+}
+
+impl FromStr for Significance {
+  type Err = Error;
+
+  fn from_str(value: &str) -> Result<Self> {
+    match value {
+      "mann-whitney" | "mann-whitney-u" => Ok(Significance::MannWhitney),
+      "welch" => Ok(Significance::Welch),
+      _ => Err(Error::InvalidSignificance {
+        test: value.to_owned(),
+      }),
+    }
+  }
+}
+
+impl Significance {
+  /// The two-sided p-value of the difference between the times of two
+  /// plugins, or `None` if either has too few times to be tested.
+  pub fn p_value(self, before: &Plugin, after: &Plugin) -> Option<f64> {
+    if before.times.len() < 2 || after.times.len() < 2 {
+      return None;
+    }
+
+    Some(match self {
+      Significance::MannWhitney => mann_whitney(&before.times, &after.times),
+      Significance::Welch => welch(&before.times, &after.times),
+    })
+  }
+}
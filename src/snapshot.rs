@@ -0,0 +1,166 @@
+use super::*;
+
+/// The plugin, phase and total startup times of a run, e.g one side of a
+/// comparison.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Snapshot {
+  pub phases: Vec<Plugin>,
+  pub plugins: Vec<Plugin>,
+  pub total: Option<Plugin>,
+}
+
+impl From<&Report> for Snapshot {
+  fn from(report: &Report) -> Self {
+    Self {
+      phases: report.phases.clone(),
+      plugins: report.plugins.clone(),
+      total: report.total.clone(),
+    }
+  }
+}
+
+impl From<Document> for Snapshot {
+  fn from(document: Document) -> Self {
+    Self {
+      phases: document.phases.into_iter().map(Plugin::from).collect(),
+      plugins: document.plugins.into_iter().map(Plugin::from).collect(),
+      total: document.total.map(Plugin::from),
+    }
+  }
+}
+
+impl Snapshot {
+  /// Fail on an entry of a file without any time, which no statistic can be
+  /// computed from.
+  fn check(self, path: &Path) -> Result<Self> {
+    if let Some(plugin) = self
+      .phases
+      .iter()
+      .chain(&self.plugins)
+      .chain(&self.total)
+      .find(|plugin| plugin.times.is_empty())
+    {
+      return Err(Error::EmptyTimes {
+        name: plugin.name.clone(),
+        path: path.to_owned(),
+      });
+    }
+
+    Ok(self)
+  }
+
+  /// Whether a path is a CSV or JSON export that can be read without
+  /// starting the editor.
+  pub fn exported(path: &Path) -> bool {
+    matches!(
+      path.extension().and_then(|extension| extension.to_str()),
+      Some("csv" | "json")
+    )
+  }
+
+  /// Read the times of a CSV export, which are written space separated in
+  /// its `Times` column.
+  pub fn from_csv(path: &Path) -> Result<Self> {
+    let mut reader = Reader::from_path(path)?;
+
+    let headers = reader.headers()?.clone();
+
+    let column = |column: &str| {
+      headers
+        .iter()
+        .position(|header| header == column)
+        .ok_or_else(|| Error::CsvColumn {
+          column: column.to_owned(),
+          path: path.to_owned(),
+        })
+    };
+
+    let (kind, name, times) =
+      (column("Kind")?, column("Plugin")?, column("Times")?);
+
+    let mut snapshot = Self::default();
+
+    for record in reader.records() {
+      let record = record?;
+
+      let plugin = Plugin::new(
+        record.get(name).unwrap_or_default().to_owned(),
+        record
+          .get(times)
+          .unwrap_or_default()
+          .split_whitespace()
+          .map(str::parse)
+          .collect::<Result<_, _>>()?,
+      );
+
+      match record.get(kind) {
+        Some("phase") => snapshot.phases.push(plugin),
+        Some("plugin") => snapshot.plugins.push(plugin),
+        Some("total") => snapshot.total = Some(plugin),
+        _ => {}
+      }
+    }
+
+    snapshot.check(path)
+  }
+
+  /// Read the times of a JSON export, see `Document`.
+  pub fn from_json(path: &Path) -> Result<Self> {
+    Self::from(serde_json::from_str::<Document>(&fs::read_to_string(
+      path,
+    )?)?)
+    .check(path)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn csv() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+
+    let path = tempdir.path().join("results.csv");
+
+    fs::write(
+      &path,
+      dedent(
+        "
+        Kind,Plugin,Max (self),Times
+        plugin,vim-just,2.00000,1.5 2
+        phase,loading plugins,4.00000,3 4
+        total,total,9.00000,8 9
+        ",
+      )
+      .trim_start(),
+    )?;
+
+    let snapshot = Snapshot::from_csv(&path)?;
+
+    assert_eq!(snapshot.phases[0].name, "loading plugins");
+    assert_eq!(snapshot.plugins.len(), 1);
+    assert_eq!(snapshot.plugins[0].name, "vim-just");
+    assert_eq!(snapshot.plugins[0].times, vec![1.5, 2.0]);
+    assert_eq!(
+      snapshot.total.map(|total| total.times),
+      Some(vec![8.0, 9.0])
+    );
+
+    fs::write(&path, "Kind,Plugin,Max (self)\nplugin,vim-just,2.00000\n")?;
+
+    assert!(matches!(
+      Snapshot::from_csv(&path),
+      Err(Error::CsvColumn { column, .. }) if column == "Times"
+    ));
+
+    fs::write(&path, "Kind,Plugin,Times\nplugin,vim-just,\n")?;
+
+    assert!(matches!(
+      Snapshot::from_csv(&path),
+      Err(Error::EmptyTimes { name, .. }) if name == "vim-just"
+    ));
+
+    Ok(())
+  }
+}
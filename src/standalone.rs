@@ -0,0 +1,11 @@
+use super::*;
+
+/// A plugin measured on its own, in an editor started without any config.
+#[derive(Debug, Clone)]
+pub(crate) struct Standalone {
+  /// The time attributed to the plugin in the log of the isolated run.
+  pub attributed: Option<Plugin>,
+  /// The total startup time without any plugin compared to the one with
+  /// only the plugin and its dependencies, named after the plugin.
+  pub cost: Change,
+}
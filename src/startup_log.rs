@@ -0,0 +1,318 @@
+use super::*;
+
+/// A single timed line of a `--startuptime` log.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Event {
+  /// Milliseconds elapsed since the editor started.
+  pub clock: f64,
+  /// Everything after the colon, e.g `sourcing /path/to/file.vim`.
+  pub description: String,
+  pub timing: Timing,
+}
+
+impl Event {
+  /// Whether `other` ran while this event was running, e.g a script that was
+  /// sourced by this script.
+  ///
+  /// Clocks are only logged with microsecond precision, so a tolerance of
+  /// one microsecond is used when comparing them.
+  pub fn contains(&self, other: &Event) -> bool {
+    let (start, end) = self.span();
+    let (other_start, other_end) = other.span();
+
+    !std::ptr::eq(self, other)
+      && start <= other_start + 0.001
+      && other_end <= end + 0.001
+  }
+
+  /// The module name of a `require('module')` event.
+  pub fn module(&self) -> Option<&str> {
+    match self.timing {
+      Timing::Sourced { .. } => self
+        .description
+        .strip_prefix("require('")
+        .and_then(|rest| rest.strip_suffix("')")),
+      Timing::Elapsed(_) => None,
+    }
+  }
+
+  /// The path of a `sourcing /path/to/file.vim` event.
+  pub fn script(&self) -> Option<&str> {
+    match self.timing {
+      Timing::Sourced { .. } => self.description.strip_prefix("sourcing "),
+      Timing::Elapsed(_) => None,
+    }
+  }
+
+  /// The start and end clock of the event.
+  pub fn span(&self) -> (f64, f64) {
+    match self.timing {
+      Timing::Elapsed(elapsed) => (self.clock - elapsed, self.clock),
+      Timing::Sourced { inclusive, .. } => (self.clock - inclusive, self.clock),
+    }
+  }
+}
+
+/// The timing columns of an event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Timing {
+  /// Two column events, e.g `000.787  000.024: parsing arguments`.
+  Elapsed(f64),
+  /// Three column events, e.g `036.484  000.043  000.043: sourcing /path`.
+  Sourced { exclusive: f64, inclusive: f64 },
+}
+
+/// The parsed contents of a `--startuptime` log.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct StartupLog {
+  pub events: Vec<Event>,
+}
+
+impl StartupLog {
+  /// Parse the contents of a `--startuptime` log.
+  ///
+  /// Lines that don't carry timing information, such as the header, are
+  /// skipped. If the log holds more than one startup, only the last one is
+  /// kept.
+  ///
+  /// 000.787  000.024: parsing arguments
+  /// 036.484  000.043  000.043: sourcing /path/to/plugin/file.vim
+  pub fn parse(content: &str) -> Result<Self> {
+    let re = RegexBuilder::new(
+      r"^\s*(\d+\.\d+)\s+(\d+\.\d+)(?:\s+(\d+\.\d+))?:\s+(.*?)\s*$",
+    )
+    .multi_line(true)
+    .build()?;
+
+    // In case the log contains windows-style path separators, they get replaced
+    // with unix-style path separators.
+    let content = content.replace('\\', "/");
+
+    let mut events = Vec::new();
+
+    for capture in re.captures_iter(&content) {
+      let clock = capture[1].parse::<f64>()?;
+
+      let timing = match capture.get(3) {
+        Some(exclusive) => Timing::Sourced {
+          exclusive: exclusive.as_str().parse()?,
+          inclusive: capture[2].parse()?,
+        },
+        None => Timing::Elapsed(capture[2].parse()?),
+      };
+
+      let description = capture[4].to_owned();
+
+      if description.ends_with("STARTING ---") {
+        events.clear();
+      }
+
+      events.push(Event {
+        clock,
+        description,
+        timing,
+      });
+    }
+
+    Ok(Self { events })
+  }
+
+  /// All `sourcing` events, along with the path of the sourced script.
+  pub fn scripts(&self) -> impl Iterator<Item = (&str, &Event)> {
+    self
+      .events
+      .iter()
+      .filter_map(|event| event.script().map(|script| (script, event)))
+  }
+
+  /// The clock at which startup finished, e.g the `--- VIM STARTED ---`
+  /// event, or the last event if startup was cut short.
+  pub fn total(&self) -> Option<f64> {
+    self
+      .events
+      .iter()
+      .rev()
+      .find(|event| event.description.ends_with("STARTED ---"))
+      .or(self.events.last())
+      .map(|event| event.clock)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn contains() -> Result<()> {
+    let content = r"
+      021.333  001.306  001.306: sourcing /usr/share/vim/vim90/colors/lists/default.vim
+      021.480  001.822  000.516: sourcing /usr/share/vim/vim90/syntax/syncolor.vim
+      021.598  002.075  000.253: sourcing /usr/share/vim/vim90/syntax/synload.vim
+      021.700  000.050  000.050: sourcing /usr/share/vim/vim90/syntax/nosyntax.vim
+    ";
+
+    let log = StartupLog::parse(&dedent(content))?;
+
+    let (default, syncolor, synload, nosyntax) = (
+      &log.events[0],
+      &log.events[1],
+      &log.events[2],
+      &log.events[3],
+    );
+
+    assert!(syncolor.contains(default));
+    assert!(synload.contains(default));
+    assert!(synload.contains(syncolor));
+    assert!(!default.contains(syncolor));
+    assert!(!synload.contains(nosyntax));
+    assert!(!synload.contains(synload));
+
+    Ok(())
+  }
+
+  #[test]
+  fn parse() -> Result<()> {
+    let content = r"
+      times in msec
+       clock   self+sourced   self:  sourced script
+       clock   elapsed:              other lines
+
+      000.007  000.007: --- VIM STARTING ---
+      000.787  000.024: parsing arguments
+      2007.575  000.509  000.509: sourcing /usr/share/vim/vim90/debian.vim
+      2007.624  001.000  000.491: sourcing /etc/vim/vimrc
+      2021.767  000.120: sourcing vimrc file(s)
+      2026.524  000.988: loading plugins
+    ";
+
+    let log = StartupLog::parse(&dedent(content))?;
+
+    assert_eq!(
+      log.events,
+      vec![
+        Event {
+          clock: 0.007,
+          description: "--- VIM STARTING ---".into(),
+          timing: Timing::Elapsed(0.007),
+        },
+        Event {
+          clock: 0.787,
+          description: "parsing arguments".into(),
+          timing: Timing::Elapsed(0.024),
+        },
+        Event {
+          clock: 2007.575,
+          description: "sourcing /usr/share/vim/vim90/debian.vim".into(),
+          timing: Timing::Sourced {
+            exclusive: 0.509,
+            inclusive: 0.509,
+          },
+        },
+        Event {
+          clock: 2007.624,
+          description: "sourcing /etc/vim/vimrc".into(),
+          timing: Timing::Sourced {
+            exclusive: 0.491,
+            inclusive: 1.0,
+          },
+        },
+        Event {
+          clock: 2021.767,
+          description: "sourcing vimrc file(s)".into(),
+          timing: Timing::Elapsed(0.120),
+        },
+        Event {
+          clock: 2026.524,
+          description: "loading plugins".into(),
+          timing: Timing::Elapsed(0.988),
+        },
+      ]
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn parse_keeps_last_startup() -> Result<()> {
+    let content = r"
+      000.008  000.008: --- NVIM STARTING ---
+      001.000  000.992: init lua interpreter
+      000.009  000.009: --- NVIM STARTING ---
+      002.000  001.991: --- NVIM STARTED ---
+    ";
+
+    let log = StartupLog::parse(&dedent(content))?;
+
+    assert_eq!(
+      log
+        .events
+        .iter()
+        .map(|event| event.description.as_str())
+        .collect::<Vec<_>>(),
+      vec!["--- NVIM STARTING ---", "--- NVIM STARTED ---"]
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn total() -> Result<()> {
+    let content = r"
+      000.008  000.008: --- NVIM STARTING ---
+      030.000  000.992: VimEnter autocommands
+      032.000  002.000: --- NVIM STARTED ---
+      040.000  008.000: first screen update
+    ";
+
+    assert_eq!(StartupLog::parse(&dedent(content))?.total(), Some(32.0));
+
+    let content = r"
+      000.007  000.007: --- VIM STARTING ---
+      027.029  000.002: editing files in windows
+    ";
+
+    assert_eq!(StartupLog::parse(&dedent(content))?.total(), Some(27.029));
+
+    assert_eq!(StartupLog::default().total(), None);
+
+    Ok(())
+  }
+
+  #[test]
+  fn parse_windows_paths() -> Result<()> {
+    let log = StartupLog::parse(
+      r"038.356  000.029  000.029: sourcing C:\Users\vimfiles\plugged\vim-just\ftdetect\just.vim",
+    )?;
+
+    assert_eq!(
+      log.scripts().map(|(script, _)| script).collect::<Vec<_>>(),
+      vec!["C:/Users/vimfiles/plugged/vim-just/ftdetect/just.vim"]
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn parse_require() -> Result<()> {
+    let log = StartupLog::parse(
+      r"010.000  001.500  000.500: require('telescope.builtin')",
+    )?;
+
+    assert_eq!(
+      log.events,
+      vec![Event {
+        clock: 10.0,
+        description: "require('telescope.builtin')".into(),
+        timing: Timing::Sourced {
+          exclusive: 0.5,
+          inclusive: 1.5,
+        },
+      }]
+    );
+
+    assert_eq!(log.events[0].module(), Some("telescope.builtin"));
+    assert_eq!(log.scripts().count(), 0);
+
+    Ok(())
+  }
+}
@@ -0,0 +1,404 @@
+use super::*;
+
+/// A small deterministic pseudo random number generator (`SplitMix64`), so
+/// resampling gives the same results on every run.
+#[derive(Debug, Clone)]
+pub(crate) struct Rng {
+  state: u64,
+}
+
+impl Rng {
+  /// A uniformly distributed index below `len`.
+  pub fn index(&mut self, len: usize) -> usize {
+    (self.next() % len.to_u64().unwrap_or(1))
+      .to_usize()
+      .unwrap_or_default()
+  }
+
+  pub fn new(seed: u64) -> Self {
+    Self { state: seed }
+  }
+
+  fn next(&mut self) -> u64 {
+    self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+    let mut z = self.state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+  }
+}
+
+/// The complementary error function, using a Chebyshev approximation with
+/// a fractional error below 1.2e-7.
+fn erfc(x: f64) -> f64 {
+  let z = x.abs();
+  let t = 1.0 / (1.0 + 0.5 * z);
+
+  let result = t
+    * (-z * z - 1.265_512_23
+      + t
+        * (1.000_023_68
+          + t
+            * (0.374_091_96
+              + t
+                * (0.096_784_18
+                  + t
+                    * (-0.186_288_06
+                      + t
+                        * (0.278_868_07
+                          + t
+                            * (-1.135_203_98
+                              + t
+                                * (1.488_515_87
+                                  + t
+                                    * (-0.822_152_23
+                                      + t * 0.170_872_77)))))))))
+      .exp();
+
+  if x >= 0.0 {
+    result
+  } else {
+    2.0 - result
+  }
+}
+
+/// The natural logarithm of the gamma function, using the Lanczos
+/// approximation.
+fn ln_gamma(x: f64) -> f64 {
+  const COEFFICIENTS: [f64; 6] = [
+    76.180_091_729_471_46,
+    -86.505_320_329_416_77,
+    24.014_098_240_830_91,
+    -1.231_739_572_450_155,
+    0.001_208_650_973_866_179,
+    -0.000_005_395_239_384_953,
+  ];
+
+  let tmp = x + 5.5;
+
+  let mut series = 1.000_000_000_190_015;
+
+  for (i, coefficient) in COEFFICIENTS.iter().enumerate() {
+    series += coefficient / (x + 1.0 + i.to_f64().unwrap_or_default());
+  }
+
+  (2.506_628_274_631_000_5 * series / x).ln() + (x + 0.5) * tmp.ln() - tmp
+}
+
+/// The two-sided p-value of the Mann-Whitney U test, the probability of
+/// drawing times at least this far apart in rank if neither set tends to be
+/// slower than the other.
+///
+/// The normal approximation is used, corrected for ties and continuity.
+pub(crate) fn mann_whitney(before: &[f64], after: &[f64]) -> f64 {
+  let mut values = before
+    .iter()
+    .map(|value| (*value, true))
+    .chain(after.iter().map(|value| (*value, false)))
+    .collect::<Vec<_>>();
+
+  values.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap());
+
+  let (mut ranks, mut ties) = (0.0, 0.0);
+
+  let mut start = 0;
+
+  while start < values.len() {
+    let end = start
+      + values[start..]
+        .iter()
+        .take_while(|(value, _)| value.total_cmp(&values[start].0).is_eq())
+        .count();
+
+    let count = (end - start).to_f64().unwrap_or_default();
+
+    let rank = (start + end + 1).to_f64().unwrap_or_default() / 2.0;
+
+    ranks += rank
+      * values[start..end]
+        .iter()
+        .filter(|(_, first)| *first)
+        .count()
+        .to_f64()
+        .unwrap_or_default();
+
+    ties += count.powi(3) - count;
+
+    start = end;
+  }
+
+  let (n1, n2) = (
+    before.len().to_f64().unwrap_or_default(),
+    after.len().to_f64().unwrap_or_default(),
+  );
+
+  let len = n1 + n2;
+
+  let u = ranks - n1 * (n1 + 1.0) / 2.0;
+
+  let sigma =
+    (n1 * n2 / 12.0 * ((len + 1.0) - ties / (len * (len - 1.0)))).sqrt();
+
+  if sigma.is_nan() || sigma == 0.0 {
+    return 1.0;
+  }
+
+  let z = ((u - n1 * n2 / 2.0).abs() - 0.5).max(0.0) / sigma;
+
+  (2.0 * (1.0 - normal_cdf(z))).min(1.0)
+}
+
+/// The cumulative distribution function of the standard normal
+/// distribution.
+pub(crate) fn normal_cdf(x: f64) -> f64 {
+  0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// The `q` quantile of some values, linearly interpolating between the two
+/// closest ones.
+pub(crate) fn quantile(values: &[f64], q: f64) -> f64 {
+  let mut values = values.to_owned();
+
+  values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+  if values.is_empty() {
+    return f64::NAN;
+  }
+
+  let position = q * (values.len() - 1).to_f64().unwrap_or_default();
+
+  let (lower, upper) = (
+    position.floor().to_usize().unwrap_or_default(),
+    position.ceil().to_usize().unwrap_or_default(),
+  );
+
+  values[lower] + (values[upper] - values[lower]) * position.fract()
+}
+
+/// The regularized incomplete beta function `I_x(a, b)`, evaluated with a
+/// continued fraction.
+pub(crate) fn regularized_beta(x: f64, a: f64, b: f64) -> f64 {
+  if x <= 0.0 {
+    return 0.0;
+  }
+
+  if x >= 1.0 {
+    return 1.0;
+  }
+
+  let front = (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b)
+    + a * x.ln()
+    + b * (1.0 - x).ln())
+  .exp();
+
+  // The continued fraction converges quickly below this point, otherwise
+  // the symmetry relation is used.
+  if x < (a + 1.0) / (a + b + 2.0) {
+    front * beta_fraction(x, a, b) / a
+  } else {
+    1.0 - front * beta_fraction(1.0 - x, b, a) / b
+  }
+}
+
+/// The continued fraction of the incomplete beta function, evaluated with
+/// the modified Lentz method.
+fn beta_fraction(x: f64, a: f64, b: f64) -> f64 {
+  const EPSILON: f64 = 1e-14;
+  const TINY: f64 = 1e-300;
+
+  let clamp = |value: f64| if value.abs() < TINY { TINY } else { value };
+
+  let mut numerators = 1.0;
+  let mut denominators = 1.0 / clamp(1.0 - (a + b) * x / (a + 1.0));
+
+  let mut fraction = denominators;
+
+  for step in 1..=300 {
+    let step = f64::from(step);
+
+    let even =
+      step * (b - step) * x / ((a + 2.0 * step - 1.0) * (a + 2.0 * step));
+
+    let odd = -(a + step) * (a + b + step) * x
+      / ((a + 2.0 * step) * (a + 2.0 * step + 1.0));
+
+    let mut delta = 1.0;
+
+    for coefficient in [even, odd] {
+      denominators = 1.0 / clamp(1.0 + coefficient * denominators);
+      numerators = clamp(1.0 + coefficient / numerators);
+      delta = denominators * numerators;
+      fraction *= delta;
+    }
+
+    if (delta - 1.0).abs() < EPSILON {
+      break;
+    }
+  }
+
+  fraction
+}
+
+/// The cumulative distribution function of Student's t distribution with
+/// `df` degrees of freedom.
+pub(crate) fn t_cdf(t: f64, df: f64) -> f64 {
+  let tail = 0.5 * regularized_beta(df / (df + t * t), df / 2.0, 0.5);
+
+  if t >= 0.0 {
+    1.0 - tail
+  } else {
+    tail
+  }
+}
+
+/// The `p` quantile of Student's t distribution with `df` degrees of
+/// freedom, found by bisecting its distribution function.
+pub(crate) fn t_quantile(p: f64, df: f64) -> f64 {
+  let (mut low, mut high) = (-1e3, 1e3);
+
+  for _ in 0..200 {
+    let mid = f64::midpoint(low, high);
+
+    if t_cdf(mid, df) < p {
+      low = mid;
+    } else {
+      high = mid;
+    }
+  }
+
+  f64::midpoint(low, high)
+}
+
+/// The two-sided p-value of Welch's t-test, the probability of drawing
+/// means at least this far apart if both sets of times had the same mean.
+///
+/// Unlike Student's t-test, the variances aren't assumed to be equal.
+pub(crate) fn welch(before: &[f64], after: &[f64]) -> f64 {
+  let moments = |values: &[f64]| {
+    let n = values.len().to_f64().unwrap_or_default();
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values
+      .iter()
+      .map(|value| (value - mean).powi(2))
+      .sum::<f64>()
+      / (n - 1.0);
+    (n, mean, variance / n)
+  };
+
+  let ((n1, mean1, error1), (n2, mean2, error2)) =
+    (moments(before), moments(after));
+
+  let error = (error1 + error2).sqrt();
+
+  if error.is_nan() {
+    return 1.0;
+  }
+
+  if error == 0.0 {
+    return if (mean1 - mean2).abs() < f64::EPSILON {
+      1.0
+    } else {
+      0.0
+    };
+  }
+
+  let df = (error1 + error2).powi(2)
+    / (error1.powi(2) / (n1 - 1.0) + error2.powi(2) / (n2 - 1.0));
+
+  let t = (mean2 - mean1) / error;
+
+  (2.0 * (1.0 - t_cdf(t.abs(), df))).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn mann_whitney_u() {
+    let (a, b) = ([1.0, 2.0, 3.0, 4.0, 5.0], [6.0, 7.0, 8.0, 9.0, 10.0]);
+
+    assert!((mann_whitney(&a, &b) - 0.012_19).abs() < 1e-4);
+    assert!((mann_whitney(&b, &a) - 0.012_19).abs() < 1e-4);
+
+    assert!((mann_whitney(&a, &a) - 1.0).abs() < 1e-6);
+    assert!(approx_eq!(f64, mann_whitney(&[1.0], &[1.0]), 1.0, ulps = 2));
+  }
+
+  #[test]
+  fn normal_distribution() {
+    let cases = vec![
+      (0.0, 0.5),
+      (1.0, 0.841_345),
+      (1.96, 0.975),
+      (-1.0, 0.158_655),
+    ];
+
+    for (x, expected) in cases {
+      assert!((normal_cdf(x) - expected).abs() < 1e-5);
+    }
+  }
+
+  #[test]
+  fn quantiles() {
+    let values = [4.0, 1.0, 3.0, 2.0, 5.0];
+
+    let cases =
+      vec![(0.0, 1.0), (0.25, 2.0), (0.5, 3.0), (0.9, 4.6), (1.0, 5.0)];
+
+    for (q, expected) in cases {
+      assert!(approx_eq!(f64, quantile(&values, q), expected, ulps = 2));
+    }
+
+    assert!(quantile(&[], 0.5).is_nan());
+  }
+
+  #[test]
+  fn rng() {
+    let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+
+    for _ in 0..100 {
+      let index = a.index(10);
+      assert!(index < 10);
+      assert_eq!(index, b.index(10));
+    }
+  }
+
+  #[test]
+  fn t_distribution() {
+    // Critical values of a two-sided 95% interval.
+    let cases = vec![
+      (1.0, 12.706),
+      (2.0, 4.303),
+      (4.0, 2.776),
+      (9.0, 2.262),
+      (29.0, 2.045),
+      (1000.0, 1.962),
+    ];
+
+    for (df, critical) in cases {
+      assert!((t_quantile(0.975, df) - critical).abs() < 1e-3);
+      assert!((t_cdf(critical, df) - 0.975).abs() < 1e-4);
+    }
+
+    assert!(approx_eq!(f64, t_cdf(0.0, 5.0), 0.5, epsilon = 1e-12));
+    assert!((t_quantile(0.025, 9.0) + 2.262).abs() < 1e-3);
+  }
+
+  #[test]
+  fn welch_t_test() {
+    let (a, b) = ([1.0, 2.0, 3.0, 4.0, 5.0], [3.0, 4.0, 5.0, 6.0, 7.0]);
+
+    assert!((welch(&a, &b) - 0.080_516).abs() < 1e-4);
+    assert!(approx_eq!(f64, welch(&a, &a), 1.0, ulps = 2));
+
+    assert!(approx_eq!(
+      f64,
+      welch(&[1.0, 1.0], &[2.0, 2.0]),
+      0.0,
+      ulps = 2
+    ));
+    assert!(approx_eq!(f64, welch(&[1.0], &[2.0]), 1.0, ulps = 2));
+  }
+}
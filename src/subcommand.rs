@@ -0,0 +1,48 @@
+use super::*;
+
+#[derive(Debug, clap::Subcommand)]
+pub(crate) enum Subcommand {
+  /// Measure the marginal cost of each plugin by starting the editor once
+  /// per plugin without it, and comparing the total startup time.
+  Ablate {
+    #[arg(long, default_value = "welch")]
+    /// The significance test, either welch or mann-whitney.
+    test: Significance,
+  },
+  /// Compare the plugin and total startup times before and after a change.
+  ///
+  /// Each side is either a JSON or CSV export, or a vimrc (init.vim,
+  /// init.lua) to start the editor with.
+  Compare {
+    /// The results or config before the change.
+    before: PathBuf,
+    /// The results or config after the change.
+    after: PathBuf,
+    #[arg(long, default_value = "welch")]
+    /// The significance test, either welch or mann-whitney.
+    test: Significance,
+  },
+  /// List every file sourced for a single plugin.
+  Explain {
+    /// The name of the plugin to explain.
+    plugin: String,
+  },
+  /// Show how the total startup time and the slowest plugins changed over
+  /// the runs recorded with `--record`.
+  History {
+    #[arg(long)]
+    /// Only show this many of the most recent runs.
+    last: Option<usize>,
+  },
+  /// Measure the standalone cost of each plugin by starting the editor once
+  /// per plugin without any config, with only that plugin and its
+  /// dependencies on the runtimepath.
+  Isolate {
+    #[arg(long = "depends")]
+    /// The plugins a plugin needs, e.g telescope.nvim=plenary.nvim.
+    depends: Vec<Dependency>,
+    #[arg(long, default_value = "welch")]
+    /// The significance test, either welch or mann-whitney.
+    test: Significance,
+  },
+}
@@ -0,0 +1,161 @@
+use super::*;
+
+/// The pseudo-terminal Vim is started in, so it starts the way it does
+/// interactively even when `vp` has no terminal, e.g in CI.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Terminal {
+  pub columns: u16,
+  pub rows: u16,
+}
+
+impl Terminal {
+  /// Start a command with a new pseudo-terminal of this size as its
+  /// standard input, output and error.
+  ///
+  /// Whatever the command writes to the terminal is read in the background
+  /// until it exits, so it never blocks on a full terminal, and returned by
+  /// the thread.
+  #[cfg(unix)]
+  pub fn spawn(self, mut command: Cmd) -> Result<(Child, JoinHandle<Vec<u8>>)> {
+    let pty = nix::pty::openpty(
+      &nix::pty::Winsize {
+        ws_col: self.columns,
+        ws_row: self.rows,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+      },
+      None,
+    )
+    .context(error::PseudoTerminalSnafu)?;
+
+    let child = command
+      .stdin(pty.slave.try_clone()?)
+      .stdout(pty.slave.try_clone()?)
+      .stderr(pty.slave)
+      .spawn()
+      .context(error::StartupTimeSnafu)?;
+
+    // The command holds the last handles to the terminal, which have to be
+    // closed for reads to stop once the child exits.
+    drop(command);
+
+    let mut master = fs::File::from(pty.master);
+
+    Ok((
+      child,
+      thread::spawn(move || {
+        let mut output = Vec::new();
+
+        // Reading fails rather than ending once the child exits and the
+        // terminal is closed, with everything written up to then read.
+        master.read_to_end(&mut output).ok();
+
+        output
+      }),
+    ))
+  }
+
+  /// Start a command with its standard error read in the background, on
+  /// platforms without pseudo-terminals.
+  #[cfg(not(unix))]
+  pub fn spawn(self, mut command: Cmd) -> Result<(Child, JoinHandle<Vec<u8>>)> {
+    let mut child = command
+      .stdout(Stdio::null())
+      .stderr(Stdio::piped())
+      .spawn()
+      .context(error::StartupTimeSnafu)?;
+
+    let mut stderr = child.stderr.take();
+
+    Ok((
+      child,
+      thread::spawn(move || {
+        let mut output = Vec::new();
+
+        if let Some(stderr) = &mut stderr {
+          stderr.read_to_end(&mut output).ok();
+        }
+
+        output
+      }),
+    ))
+  }
+}
+
+impl Default for Terminal {
+  fn default() -> Self {
+    Self {
+      columns: 80,
+      rows: 24,
+    }
+  }
+}
+
+impl Display for Terminal {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{}x{}", self.columns, self.rows)
+  }
+}
+
+impl FromStr for Terminal {
+  type Err = Error;
+
+  fn from_str(value: &str) -> Result<Self> {
+    value
+      .split_once('x')
+      .and_then(|(columns, rows)| {
+        Some(Self {
+          columns: columns.parse().ok().filter(|columns| *columns > 0)?,
+          rows: rows.parse().ok().filter(|rows| *rows > 0)?,
+        })
+      })
+      .ok_or_else(|| Error::InvalidTerminal {
+        size: value.to_owned(),
+      })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn from_str() {
+    assert_eq!(
+      "120x40".parse::<Terminal>().unwrap(),
+      Terminal {
+        columns: 120,
+        rows: 40
+      }
+    );
+
+    for value in ["", "80", "80x", "x24", "0x24", "80x24x1", "-1x24"] {
+      assert!(matches!(
+        value.parse::<Terminal>(),
+        Err(Error::InvalidTerminal { size }) if size == value
+      ));
+    }
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn spawn() -> Result<()> {
+    let mut command = Cmd::new("sh");
+
+    command
+      .arg("-c")
+      .arg("[ -t 0 ] && [ -t 1 ] && stty size | grep -qx '40 120'");
+
+    let (mut child, reader) = Terminal {
+      columns: 120,
+      rows: 40,
+    }
+    .spawn(command)?;
+
+    assert!(child.wait()?.success());
+
+    assert!(reader.join().unwrap().is_empty());
+
+    Ok(())
+  }
+}
@@ -0,0 +1,46 @@
+use super::*;
+
+/// Which column of a `sourcing` event is used to time a plugin.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) enum TimingMode {
+  /// The `self` column, the time spent in the script itself.
+  #[default]
+  Exclusive,
+  /// The `self+sourced` column, which includes every script sourced by the
+  /// script.
+  Inclusive,
+}
+
+impl Display for TimingMode {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      TimingMode::Exclusive => write!(f, "self"),
+      TimingMode::Inclusive => write!(f, "self+sourced"),
+    }
+  }
+}
+
+impl TimingMode {
+  /// The time of an event according to this mode.
+  pub fn time(self, event: &Event) -> f64 {
+    match (self, event.timing) {
+      (TimingMode::Exclusive, Timing::Sourced { exclusive, .. }) => exclusive,
+      (TimingMode::Inclusive, Timing::Sourced { inclusive, .. }) => inclusive,
+      (_, Timing::Elapsed(elapsed)) => elapsed,
+    }
+  }
+}
+
+impl FromStr for TimingMode {
+  type Err = Error;
+
+  fn from_str(value: &str) -> Result<Self> {
+    match value {
+      "exclusive" | "self" => Ok(TimingMode::Exclusive),
+      "inclusive" | "self+sourced" => Ok(TimingMode::Inclusive),
+      _ => Err(Error::InvalidTimingMode {
+        mode: value.to_owned(),
+      }),
+    }
+  }
+}
@@ -0,0 +1,270 @@
+use super::*;
+
+/// An event of the startup log along with everything that ran during it.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Node {
+  pub children: Vec<Node>,
+  /// The clock at which the event finished.
+  pub end: f64,
+  pub event: Event,
+  /// The clock at which the event started.
+  pub start: f64,
+}
+
+impl Node {
+  /// The category of the event, either a phase, script or require.
+  pub fn category(&self) -> &'static str {
+    if self.event.script().is_some() {
+      "script"
+    } else if self.event.module().is_some() {
+      "require"
+    } else {
+      "phase"
+    }
+  }
+
+  /// The time spent in the event itself, excluding its children.
+  pub fn exclusive(&self) -> f64 {
+    match self.event.timing {
+      Timing::Sourced { exclusive, .. } => exclusive,
+      Timing::Elapsed(_) => (self.end
+        - self.start
+        - self
+          .children
+          .iter()
+          .map(|child| child.end - child.start)
+          .sum::<f64>())
+      .max(0.0),
+    }
+  }
+
+  /// The name of the node as a frame in a stack trace.
+  pub fn frame(&self) -> String {
+    self
+      .event
+      .script()
+      .unwrap_or(&self.event.description)
+      .replace(';', ":")
+  }
+}
+
+/// The nesting of events in a startup log, e.g the vimrc sourcing a plugin
+/// file which in turn sources an autoload file.
+///
+/// The nesting isn't logged explicitly, so it's reconstructed from the clock
+/// and self+sourced columns. A script is only logged once it has finished,
+/// so everything it sourced is logged right before it, e.g:
+///
+/// 021.333  001.306  001.306: sourcing .../colors/lists/default.vim
+/// 021.480  001.822  000.516: sourcing .../syntax/syncolor.vim
+/// 021.598  002.075  000.253: sourcing .../syntax/synload.vim
+///
+/// Two column events are treated as spanning everything since the previous
+/// two column event, which makes the startup phases the roots of the tree.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Tree {
+  pub roots: Vec<Node>,
+}
+
+impl Tree {
+  /// Every path from a root to a node, separated by semicolons, along with
+  /// the exclusive time of the last node of the path.
+  pub fn folded(&self) -> Vec<(String, f64)> {
+    fn walk(node: &Node, prefix: &str, stacks: &mut Vec<(String, f64)>) {
+      let stack = if prefix.is_empty() {
+        node.frame()
+      } else {
+        format!("{prefix};{}", node.frame())
+      };
+
+      for child in &node.children {
+        walk(child, &stack, stacks);
+      }
+
+      stacks.push((stack, node.exclusive()));
+    }
+
+    let mut stacks = Vec::new();
+
+    for root in &self.roots {
+      walk(root, "", &mut stacks);
+    }
+
+    stacks
+  }
+
+  pub fn new(log: &StartupLog) -> Self {
+    let mut pending: Vec<Node> = Vec::new();
+
+    let mut previous = 0.0;
+
+    for event in &log.events {
+      let (start, end) = match event.timing {
+        Timing::Elapsed(_) => (previous, event.clock),
+        Timing::Sourced { .. } => event.span(),
+      };
+
+      if let Timing::Elapsed(_) = event.timing {
+        previous = event.clock;
+      }
+
+      let mut children = Vec::new();
+
+      while let Some(last) = pending.last() {
+        if let Timing::Elapsed(_) = last.event.timing {
+          break;
+        }
+
+        if last.start + 0.001 < start || last.end > end + 0.001 {
+          break;
+        }
+
+        children.extend(pending.pop());
+      }
+
+      children.reverse();
+
+      pending.push(Node {
+        children,
+        end,
+        event: event.clone(),
+        start,
+      });
+    }
+
+    Self { roots: pending }
+  }
+
+  /// Every node of the tree, parents before their children.
+  pub fn nodes(&self) -> Vec<&Node> {
+    fn walk<'a>(node: &'a Node, nodes: &mut Vec<&'a Node>) {
+      nodes.push(node);
+
+      for child in &node.children {
+        walk(child, nodes);
+      }
+    }
+
+    let mut nodes = Vec::new();
+
+    for root in &self.roots {
+      walk(root, &mut nodes);
+    }
+
+    nodes
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn names(nodes: &[Node]) -> Vec<String> {
+    nodes.iter().map(Node::frame).collect()
+  }
+
+  fn setup() -> Result<Tree> {
+    let content = r"
+      000.007  000.007: --- VIM STARTING ---
+      000.787  000.780: parsing arguments
+      000.787  000.000: expanding arguments
+      006.558  000.126: init highlight
+      007.575  000.509  000.509: sourcing /usr/share/vim/vim90/debian.vim
+      010.333  001.306  001.306: sourcing /usr/share/vim/vim90/colors/lists/default.vim
+      010.480  001.822  000.516: sourcing /usr/share/vim/vim90/syntax/syncolor.vim
+      010.598  002.075  000.253: sourcing /usr/share/vim/vim90/syntax/synload.vim
+      010.700  003.000  000.925: sourcing /home/.vimrc
+      010.767  000.120: sourcing vimrc file(s)
+    ";
+
+    Ok(Tree::new(&StartupLog::parse(&dedent(content))?))
+  }
+
+  #[test]
+  fn new() -> Result<()> {
+    let tree = setup()?;
+
+    assert_eq!(
+      names(&tree.roots),
+      vec![
+        "--- VIM STARTING ---",
+        "parsing arguments",
+        "expanding arguments",
+        "init highlight",
+        "sourcing vimrc file(s)"
+      ]
+    );
+
+    let vimrc = &tree.roots[4];
+
+    assert_eq!(
+      names(&vimrc.children),
+      vec!["/usr/share/vim/vim90/debian.vim", "/home/.vimrc"]
+    );
+
+    let synload = &vimrc.children[1].children[0];
+
+    assert_eq!(
+      names(&synload.children),
+      vec!["/usr/share/vim/vim90/syntax/syncolor.vim"]
+    );
+
+    assert_eq!(
+      names(&synload.children[0].children),
+      vec!["/usr/share/vim/vim90/colors/lists/default.vim"]
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn nodes() -> Result<()> {
+    let tree = setup()?;
+
+    let nodes = tree.nodes();
+
+    assert_eq!(nodes.len(), 10);
+
+    for (i, node) in nodes.iter().enumerate() {
+      for child in &node.children {
+        assert!(nodes[..i].iter().all(|node| !std::ptr::eq(*node, child)));
+      }
+    }
+
+    assert_eq!(nodes[0].category(), "phase");
+    assert_eq!(nodes[5].category(), "script");
+
+    Ok(())
+  }
+
+  #[test]
+  fn folded() -> Result<()> {
+    let stacks = setup()?.folded();
+
+    let cases = vec![
+      ("--- VIM STARTING ---", 0.007),
+      ("parsing arguments", 0.78),
+      ("expanding arguments", 0.0),
+      ("init highlight", 5.771),
+      (
+        "sourcing vimrc file(s);/usr/share/vim/vim90/debian.vim",
+        0.509,
+      ),
+      (
+        "sourcing vimrc file(s);/home/.vimrc;/usr/share/vim/vim90/syntax/synload.vim",
+        0.253,
+      ),
+      ("sourcing vimrc file(s);/home/.vimrc", 0.925),
+      ("sourcing vimrc file(s)", 0.7),
+    ];
+
+    for (stack, time) in cases {
+      let (_, value) = stacks.iter().find(|(name, _)| name == stack).unwrap();
+      assert!(approx_eq!(f64, *value, time, epsilon = 1e-9));
+    }
+
+    assert_eq!(stacks.len(), 10);
+
+    Ok(())
+  }
+}
@@ -1,5 +1,49 @@
 use super::*;
 
+/// Formats seconds since the unix epoch as a UTC date and time, e.g
+/// `2024-03-01 14:05`.
+pub(crate) fn date(timestamp: u64) -> String {
+  let (days, seconds) = (timestamp / 86_400, timestamp % 86_400);
+
+  // Convert days since the epoch to a civil date, see
+  // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+  let days = days + 719_468;
+  let era = days / 146_097;
+  let day_of_era = days - era * 146_097;
+  let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524
+    - day_of_era / 146_096)
+    / 365;
+  let day_of_year =
+    day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+  let shifted_month = (5 * day_of_year + 2) / 153;
+  let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+  let month = if shifted_month < 10 {
+    shifted_month + 3
+  } else {
+    shifted_month - 9
+  };
+  let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+  format!(
+    "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+    seconds / 3600,
+    seconds % 3600 / 60
+  )
+}
+
+/// Escapes a path for use in an Ex command, e.g in an autocommand pattern
+/// or a comma separated option like `runtimepath`.
+pub(crate) fn escape(path: &str) -> String {
+  path.chars().fold(String::new(), |mut escaped, c| {
+    if matches!(c, ' ' | ',' | '\\' | '|' | '"') {
+      escaped.push('\\');
+    }
+
+    escaped.push(c);
+    escaped
+  })
+}
+
 /// Repeats the string `s`, `n` times.
 pub(crate) fn repeat(s: &str, n: usize) -> String {
   iter::repeat_n(s, n).collect::<Vec<_>>().join("")
@@ -9,6 +53,33 @@ pub(crate) fn repeat(s: &str, n: usize) -> String {
 mod tests {
   use super::*;
 
+  #[test]
+  fn test_date() {
+    let cases = vec![
+      (0, "1970-01-01 00:00"),
+      (951_782_400, "2000-02-29 00:00"),
+      (1_709_301_900, "2024-03-01 14:05"),
+      (1_735_689_599, "2024-12-31 23:59"),
+    ];
+
+    for (timestamp, expected) in cases {
+      assert_eq!(date(timestamp), expected);
+    }
+  }
+
+  #[test]
+  fn test_escape() {
+    let cases = vec![
+      ("/home/user/.vim", "/home/user/.vim"),
+      ("/home/user/my plugins", "/home/user/my\\ plugins"),
+      ("/a,b|c", "/a\\,b\\|c"),
+    ];
+
+    for (path, expected) in cases {
+      assert_eq!(escape(path), expected);
+    }
+  }
+
   #[test]
   fn test_repeat() {
     let cases = vec![
@@ -0,0 +1,58 @@
+use super::*;
+
+/// A startup time that went over its budget.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Violation {
+  /// A startup phase took longer than its budget.
+  Phase {
+    budget: f64,
+    name: String,
+    time: f64,
+  },
+  /// A plugin took longer than its budget.
+  Plugin {
+    budget: f64,
+    name: String,
+    time: f64,
+  },
+  /// A plugin, or the total startup time, got slower than the baseline by
+  /// more than the allowed percentage.
+  Regression {
+    baseline: f64,
+    maximum: f64,
+    name: String,
+    time: f64,
+  },
+  /// The total startup time went over its budget.
+  Total { budget: f64, time: f64 },
+}
+
+impl Display for Violation {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Violation::Phase { budget, name, time } => write!(
+        f,
+        "{name} phase took {time:.2} ms, over its budget of {budget:.2} ms"
+      ),
+      Violation::Plugin { budget, name, time } => write!(
+        f,
+        "{name} took {time:.2} ms, over its budget of {budget:.2} ms"
+      ),
+      Violation::Regression {
+        baseline,
+        maximum,
+        name,
+        time,
+      } => write!(
+        f,
+        "{name} regressed by {:.1}% ({baseline:.2} ms -> {time:.2} ms), over \
+         the maximum of {maximum}%",
+        (time - baseline) / baseline * 100.0
+      ),
+      Violation::Total { budget, time } => write!(
+        f,
+        "total startup took {time:.2} ms, over the budget of {budget:.2} ms"
+      ),
+    }
+  }
+}
@@ -2,124 +2,139 @@ use super::*;
 
 #[derive(Debug)]
 pub(crate) struct Worker {
-  command: Command,
-  file: Option<PathBuf>,
-  iter: i64,
-  sys: bool,
+  /// Extra arguments passed to the editor.
+  pub arguments: Vec<String>,
+  /// A vimrc to start the editor with instead of the default one.
+  pub config: Option<PathBuf>,
+  pub editor: Editor,
+  pub estimator: Estimator,
+  pub file: Option<PathBuf>,
+  pub iter: i64,
+  pub outliers: Outliers,
+  pub plugin_directories: Vec<PathBuf>,
+  pub sys: bool,
+  pub system_directories: Vec<PathBuf>,
+  pub terminal: Terminal,
+  /// How long a single start of the editor may take before it's killed.
+  pub timeout: Duration,
+  pub timing: TimingMode,
+  pub warmup: i64,
 }
 
-impl Worker {
-  /// Clean up the created `vim.log` file.
-  fn clean() -> Result<()> {
-    fs::remove_file("vim.log").context(error::RemoveLogSnafu)?;
-    Ok(())
-  }
-
-  pub fn new(
-    command: Command,
-    iter: i64,
-    sys: bool,
-    file: Option<PathBuf>,
-  ) -> Self {
+#[cfg(test)]
+impl Default for Worker {
+  fn default() -> Self {
     Self {
-      command,
-      file,
-      iter,
-      sys,
+      arguments: Vec::new(),
+      config: None,
+      editor: Command::Vim.into(),
+      estimator: Estimator::default(),
+      file: None,
+      iter: 1,
+      outliers: Outliers::default(),
+      plugin_directories: Vec::new(),
+      sys: false,
+      system_directories: Vec::new(),
+      terminal: Terminal::default(),
+      timeout: Duration::from_secs(30),
+      timing: TimingMode::default(),
+      warmup: 0,
     }
   }
+}
 
-  /// Parse the contents of `vim.log`.
-  ///
-  /// 036.484  000.043  000.043: sourcing /path/to/plugin/file.vim
-  ///                   ^^^^^^^                    ^^^^^^
-  pub fn parse(&self) -> Result<HashMap<String, f64>> {
-    let content = fs::read_to_string("vim.log").context(error::ReadLogSnafu)?;
-
-    // In case the log contains windows-style path separators, they get replaced
-    // with unix-style path separators. This saves us from a more complicated regex
-    // pattern later on.
-    let content = content.replace('\\', "/");
-
-    if let Some(plugin_directory) = Self::plugin_directory(&content)? {
-      let re = RegexBuilder::new(&format!(
-        r"^\d+.\d+\s+\d+.\d+\s+(\d+.\d+): sourcing {plugin_directory}/([^/]+)/",
-      ))
-      .multi_line(true)
-      .build()?;
-
-      let mut plugins = HashMap::new();
-      for capture in re.captures_iter(&content) {
-        if let (Some(time), Some(plugin)) = (capture.get(1), capture.get(2)) {
-          *plugins.entry(plugin.as_str().to_owned()).or_insert(0.0) +=
-            time.as_str().parse::<f64>()?;
-        }
-      }
-
-      if self.sys {
-        for dir in &["/usr", "/usr/local"] {
-          let re = RegexBuilder::new(&format!(
-            r"^\d+.\d+\s+\d+.\d+\s+(\d+.\d+): sourcing {dir}/.+/([^/]+.vim)\n",
-          ))
-          .multi_line(true)
-          .build()?;
-
-          for capture in re.captures_iter(&content) {
-            if let (Some(time), Some(plugin)) = (capture.get(1), capture.get(2))
-            {
-              *plugins.entry(plugin.as_str().to_owned()).or_insert(0.0) +=
-                time.as_str().parse::<f64>()?;
-            }
-          }
-        }
+impl Worker {
+  /// Shorten a directory for display by replacing the home directory with
+  /// `~`.
+  fn abbreviate(directory: &str, home: Option<&str>) -> String {
+    match home.and_then(|home| directory.strip_prefix(home)) {
+      Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+        format!("~{rest}")
       }
-
-      return Ok(plugins);
+      _ => directory.to_owned(),
     }
+  }
 
-    Err(error::Error::PluginDirectory)
+  /// Clean up the created startup log.
+  fn clean(path: &Path) -> Result<()> {
+    fs::remove_file(path).context(error::RemoveLogSnafu { path })?;
+    Ok(())
   }
 
-  /// Grabs the plugin directory from the `vim.log` files contents.
+  /// Parse the contents of a startup log.
+  pub fn parse(
+    &self,
+    path: &Path,
+    runtimepath: &Runtimepath,
+  ) -> Result<Sample> {
+    self.sample(
+      &StartupLog::parse(
+        &fs::read_to_string(path).context(error::ReadLogSnafu { path })?,
+      )?,
+      runtimepath,
+    )
+  }
+
+  /// Grabs the plugin directories from the startup log.
   ///
-  /// In order to get the plugin directory we simply need to
-  /// grab the most common directory that shows up in the
-  /// `vim.log` file that is not a subdirectory of a system plugin
-  /// directory e.g /usr or /usr/local.
-  pub fn plugin_directory(content: &str) -> Result<Option<String>> {
+  /// A plugin directory is the parent of a directory holding an `autoload`,
+  /// `ftdetect`, `plugin` or `syntax` directory, optionally nested in
+  /// `after`, e.g both `~/.vim/plugged` and `~/.vim/pack/bundle/start`.
+  ///
+  /// Directories under a system plugin directory, e.g /usr or /usr/local,
+  /// and directories that contain another plugin directory, e.g `~` for
+  /// scripts in `~/.vim/plugin`, are skipped. The most common directories
+  /// come first.
+  pub fn plugin_directories(log: &StartupLog) -> Result<Vec<String>> {
     let re = RegexBuilder::new(
-      r"^\d+.\d+\s+\d+.\d+\s+\d+.\d+: sourcing (.+?)/(?:[^/]+/)(?:autoload|ftdetect|plugin|syntax)/[^/]+",
-    ).multi_line(true).build()?;
+      r"^(.+?)/(?:[^/]+/)(?:after/)?(?:autoload|ftdetect|plugin|syntax)/[^/]+",
+    )
+    .build()?;
 
     let mut counts = HashMap::new();
-    for capture in re.captures_iter(content) {
-      if let Some(directory) = capture.get(1) {
+    for (script, _) in log.scripts() {
+      if let Some(directory) = re.captures(script).and_then(|c| c.get(1)) {
         if !directory.as_str().starts_with("/usr") {
           *counts.entry(directory.as_str()).or_insert(0) += 1;
         }
       }
     }
 
+    let mut directories = counts
+      .iter()
+      .filter(|(directory, _)| {
+        !counts
+          .keys()
+          .any(|other| other.starts_with(&format!("{directory}/")))
+      })
+      .map(|(directory, count)| ((*directory).to_owned(), *count))
+      .collect::<Vec<_>>();
+
+    directories.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
     Ok(
-      counts
-        .iter()
-        .max_by(|a, b| a.1.cmp(b.1))
-        .map(|(k, _v)| k.to_string()),
+      directories
+        .into_iter()
+        .map(|(directory, _)| directory)
+        .collect(),
     )
   }
 
   /// Execute `vim --startuptime` in a child process.
-  /// Upon executing this command a `vim.log` file should be parsed
-  /// and relevant data should be returned as a map.
+  /// Upon executing this command the startup log should be parsed
+  /// and relevant data should be returned as a report.
   ///
-  /// This will accumulate each value over all iterations and finally
-  /// return the map with average values.
-  pub fn run(&self) -> Result<Vec<Plugin>> {
-    let mut ret = HashMap::new();
+  /// The log is written to a private temporary directory, so concurrent
+  /// runs don't clobber each other and nothing is written to the working
+  /// directory.
+  ///
+  /// This will accumulate each value over all iterations.
+  pub fn run(&self) -> Result<Report> {
+    let (mut samples, mut warmup) = (Vec::new(), Vec::new());
 
     info!(
       "Executing `{} --startuptime` and parsing the log file {} time{}",
-      self.command,
+      self.editor,
       self.iter,
       if self.iter > 1 { "s" } else { "" }
     );
@@ -132,43 +147,357 @@ impl Worker {
       .unwrap_or("")
       .to_owned();
 
-    for _ in 0..self.iter {
-      let mut child = Cmd::new(format!("{}", self.command))
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
+    let mut runtimepath = match self.editor.command {
+      Command::Neovim => Runtimepath::query(
+        &self.editor,
+        self.file.as_deref(),
+        self.config.as_deref(),
+        &self.arguments,
+        self.timeout,
+      )
+      .unwrap_or_else(|error| {
+        info!("Failed to query the runtimepath: {error}");
+        Runtimepath::default()
+      }),
+      Command::Vim => Runtimepath::default(),
+    };
+
+    runtimepath.vimruntime =
+      Runtimepath::vimruntime(&self.editor, &self.arguments, self.timeout)
+        .unwrap_or_else(|error| {
+          info!("Failed to query $VIMRUNTIME: {error}");
+          None
+        });
+
+    let directory = tempfile::Builder::new()
+      .prefix("vim-profiler")
+      .tempdir()
+      .context(error::TempDirSnafu)?;
+
+    let log = directory.path().join("vim.log");
+
+    for i in 0..self.warmup + self.iter {
+      let mut command = self.editor.command();
+
+      command
+        .args(
+          self
+            .config
+            .iter()
+            .flat_map(|config| [Path::new("-u"), config.as_path()]),
+        )
+        .args(&self.arguments)
         .arg(&file)
         .arg("--startuptime")
-        .arg("vim.log")
+        .arg(&log)
         .arg("-f")
         .arg("-c")
-        .arg("q")
-        .spawn()
-        .context(error::StartupTimeSnafu)?;
+        .arg("q");
+
+      // The editor gets its own process group, so it can be killed along
+      // with anything it started that still holds its output open.
+      #[cfg(unix)]
+      std::os::unix::process::CommandExt::process_group(&mut command, 0);
+
+      // Neovim starts the same without a UI, while Vim needs a terminal to
+      // start the way it does interactively.
+      let (mut child, output) = match self.editor.command {
+        Command::Neovim => {
+          command
+            .arg("--headless")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+
+          let mut child = command.spawn().context(error::StartupTimeSnafu)?;
+
+          let stderr = Editor::read(child.stderr.take());
+
+          (child, stderr)
+        }
+        Command::Vim => self.terminal.spawn(command)?,
+      };
+
+      let messages = Editor::messages(&self.editor.wait(
+        &mut child,
+        output,
+        self.timeout,
+      )?)?;
 
-      child.wait()?;
+      if !messages.is_empty() {
+        info!("`{}` wrote:\n{messages}", self.editor);
+      }
 
-      let plugins = self.parse()?;
+      let sample = self.parse(&log, &runtimepath)?;
 
-      for (k, v) in &plugins {
-        ret.entry(k.to_owned()).or_insert_with(Vec::new).push(*v);
+      if i < self.warmup {
+        warmup.push(sample);
+      } else {
+        samples.push(sample);
       }
 
-      Self::clean()?;
+      Self::clean(&log)?;
     }
 
-    Ok(
-      ret
+    Ok(Report::new(
+      &samples,
+      &warmup,
+      Run {
+        command: self.editor.command.clone(),
+        estimator: self.estimator,
+        file: self.file.clone(),
+        iterations: self.iter,
+        outliers: self.outliers,
+        sys: self.sys,
+        timing: self.timing,
+        version: self.editor.version.clone(),
+        warmup: self.warmup,
+      },
+    ))
+  }
+
+  /// Attribute the scripts sourced and lua modules required in a log to
+  /// plugins.
+  ///
+  /// 036.484  000.043  000.043: sourcing /path/to/plugin/file.vim
+  ///          ^^^^^^^  ^^^^^^^                    ^^^^^^
+  /// 041.012  000.310  000.120: require('telescope.builtin')
+  ///
+  /// Modules are resolved to the `runtimepath` entry that provides them,
+  /// falling back to the directories inside of the plugin directory. An
+  /// entry that isn't directly inside a plugin directory, e.g one added with
+  /// `rtp:prepend`, is a plugin of its own named after its last component.
+  ///
+  /// Depending on the timing mode either the self+sourced or the self column
+  /// is used. With self+sourced, scripts sourced by another script of the
+  /// same plugin are skipped so their time isn't counted twice.
+  fn sample(
+    &self,
+    log: &StartupLog,
+    runtimepath: &Runtimepath,
+  ) -> Result<Sample> {
+    let mut directories = self
+      .plugin_directories
+      .iter()
+      .map(|directory| {
+        directory
+          .display()
+          .to_string()
+          .trim_end_matches('/')
+          .to_owned()
+      })
+      .collect::<Vec<_>>();
+
+    let system = self.system_directories(runtimepath);
+
+    for directory in Self::plugin_directories(log)? {
+      let overlaps = |other: &String| {
+        directory == *other
+          || directory.starts_with(&format!("{other}/"))
+          || other.starts_with(&format!("{directory}/"))
+      };
+
+      if !directories.contains(&directory) && !system.iter().any(overlaps) {
+        directories.push(directory);
+      }
+    }
+
+    if directories.is_empty() {
+      return Err(Error::PluginDirectory);
+    }
+
+    let fallback;
+
+    let runtimepath = if runtimepath.entries.is_empty() {
+      fallback = Runtimepath {
+        entries: directories
+          .iter()
+          .flat_map(|directory| {
+            Runtimepath::plugins(Path::new(directory)).entries
+          })
+          .collect(),
+        vimruntime: runtimepath.vimruntime.clone(),
+      };
+      &fallback
+    } else {
+      runtimepath
+    };
+
+    let mut sources = Vec::new();
+
+    for event in &log.events {
+      if let Some(script) = event.script() {
+        sources.push((script.to_owned(), None, event));
+      } else if let Some((entry, file)) = event
+        .module()
+        .and_then(|module| runtimepath.resolve(module))
+      {
+        let entry = entry.display().to_string();
+        sources.push((format!("{entry}/{file}"), Some(entry.len()), event));
+      }
+    }
+
+    let mut scripts = Vec::new();
+
+    for (script, entry, event) in &sources {
+      let directory = directories
         .iter()
-        .map(|(k, v)| Plugin::new(k.to_owned(), v.to_owned()))
-        .collect::<Vec<Plugin>>(),
-    )
+        .filter(|directory| script.starts_with(&format!("{directory}/")))
+        .max_by_key(|directory| directory.len())
+        .filter(|directory| {
+          entry.is_none_or(|entry| {
+            script[..entry]
+              .rsplit_once('/')
+              .is_some_and(|(parent, _)| parent == directory.as_str())
+          })
+        });
+
+      let (name, file, directory) = match directory {
+        Some(directory) => {
+          match script[directory.len() + 1..].split_once('/') {
+            Some((name, file)) => (name, file, Some(directory.as_str())),
+            None => continue,
+          }
+        }
+        None => match (Self::system_script(&system, script), entry) {
+          (Some((directory, name)), _) if self.sys => {
+            (name, script.as_str(), Some(directory))
+          }
+          (None, Some(entry)) => match script[..*entry].rsplit_once('/') {
+            Some((directory, name)) => {
+              (name, &script[entry + 1..], Some(directory))
+            }
+            None => continue,
+          },
+          _ => continue,
+        },
+      };
+
+      scripts.push((name, file, directory, event));
+    }
+
+    let mut roots = HashMap::new();
+
+    for (name, _, directory, _) in &scripts {
+      roots.entry(*name).or_insert_with(Vec::new).push(*directory);
+    }
+
+    let home = env::var("HOME").ok();
+
+    let scripts = scripts
+      .iter()
+      .map(|(name, file, directory, event)| {
+        let mut directories = roots[name].clone();
+
+        directories.sort_unstable();
+        directories.dedup();
+
+        let path = directory.map(|directory| Path::new(directory).join(name));
+
+        let name = match directory {
+          Some(directory) if directories.len() > 1 => {
+            format!("{name} ({})", Self::abbreviate(directory, home.as_deref()))
+          }
+          _ => (*name).to_owned(),
+        };
+
+        (name, *file, path, *event)
+      })
+      .collect::<Vec<_>>();
+
+    let mut files = HashMap::new();
+    let mut paths = HashMap::new();
+    let mut plugins = HashMap::new();
+
+    for (i, (name, file, path, event)) in scripts.iter().enumerate() {
+      let time = self.timing.time(event);
+
+      if let Some(path) = path {
+        paths.entry(name.clone()).or_insert_with(|| path.clone());
+      }
+
+      *files
+        .entry(name.clone())
+        .or_insert_with(HashMap::new)
+        .entry((*file).to_owned())
+        .or_insert(0.0) += time;
+
+      let nested = self.timing == TimingMode::Inclusive
+        && scripts[i + 1..]
+          .iter()
+          .any(|(parent, _, _, outer)| parent == name && outer.contains(event));
+
+      if !nested {
+        *plugins.entry(name.clone()).or_insert(0.0) += time;
+      }
+    }
+
+    Ok(Sample {
+      files,
+      paths,
+      phases: Phase::durations(log),
+      plugins,
+      total: log.total(),
+      tree: Tree::new(log),
+    })
+  }
+
+  /// The directories holding the runtime files of the editor, most
+  /// specific first: `$VIMRUNTIME`, the ones given with `--sys-dir` and
+  /// finally /usr/local and /usr.
+  fn system_directories(&self, runtimepath: &Runtimepath) -> Vec<String> {
+    let mut directories = runtimepath
+      .vimruntime
+      .iter()
+      .chain(&self.system_directories)
+      .map(|directory| {
+        directory
+          .display()
+          .to_string()
+          .trim_end_matches('/')
+          .to_owned()
+      })
+      .collect::<Vec<_>>();
+
+    for directory in ["/usr/local", "/usr"] {
+      if !directories.iter().any(|other| other == directory) {
+        directories.push(directory.to_owned());
+      }
+    }
+
+    directories
+  }
+
+  /// The most specific system directory holding a script, along with the
+  /// path of the script relative to it, e.g `syntax/syntax.vim` for
+  /// `$VIMRUNTIME/syntax/syntax.vim`.
+  fn system_script<'a>(
+    directories: &'a [String],
+    script: &'a str,
+  ) -> Option<(&'a str, &'a str)> {
+    let path = Path::new(script);
+
+    if !path
+      .extension()
+      .is_some_and(|extension| extension == "vim" || extension == "lua")
+    {
+      return None;
+    }
+
+    directories
+      .iter()
+      .filter_map(|directory| {
+        script
+          .strip_prefix(&format!("{directory}/"))
+          .map(|rest| (directory.as_str(), rest))
+      })
+      .min_by_key(|(_, rest)| rest.len())
   }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
-  use std::io::prelude::*;
 
   #[test]
   fn plugin_directory() -> Result<()> {
@@ -182,8 +511,25 @@ mod tests {
     ";
 
     assert_eq!(
-      Worker::plugin_directory(&dedent(content))?.unwrap(),
-      "/Users/.vim/plugged"
+      Worker::plugin_directories(&StartupLog::parse(&dedent(content))?)?,
+      vec!["/Users/.vim/plugged"]
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn plugin_directories() -> Result<()> {
+    let content = r"
+      038.356  000.029  000.029: sourcing /Users/.vim/plugged/vim-prettier/ftdetect/graphql.vim
+      039.955  000.028  000.028: sourcing /Users/.vim/plugged/vim-polyglot/ftdetect/polyglot.vim
+      040.327  000.058  000.058: sourcing /Users/.vim/pack/bundle/opt/vim-markdown/after/ftdetect/markdown.vim
+      040.530  000.048  000.048: sourcing /Users/.vim/plugin/rust.vim
+    ";
+
+    assert_eq!(
+      Worker::plugin_directories(&StartupLog::parse(&dedent(content))?)?,
+      vec!["/Users/.vim/plugged", "/Users/.vim/pack/bundle/opt"]
     );
 
     Ok(())
@@ -191,7 +537,7 @@ mod tests {
 
   #[test]
   fn plugin_directory_empty_content() -> Result<()> {
-    assert!(Worker::plugin_directory("")?.is_none());
+    assert!(Worker::plugin_directories(&StartupLog::default())?.is_empty());
     Ok(())
   }
 
@@ -211,15 +557,230 @@ mod tests {
       ("rust.vim", 0.048),
     ];
 
-    let mut file = fs::File::create("vim.log")?;
-    file.write_all(dedent(content).as_bytes())?;
+    let tempdir = tempfile::tempdir()?;
+
+    let log = tempdir.path().join("vim.log");
+
+    fs::write(&log, dedent(content))?;
+
+    let data = Worker::default().parse(&log, &Runtimepath::default())?;
+    for (key, value) in cases {
+      assert!(approx_eq!(f64, data.plugins[key], value, ulps = 2));
+    }
+
+    assert_eq!(
+      data.files["vim-markdown"].keys().collect::<Vec<_>>(),
+      vec!["ftdetect/markdown.vim"]
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn sample_inclusive() -> Result<()> {
+    let content = r"
+      040.000  002.000  002.000: sourcing /Users/.vim/plugged/vim-lsp/autoload/lsp.vim
+      041.000  001.000  001.000: sourcing /Users/.vim/plugged/async.vim/autoload/async.vim
+      045.000  008.000  003.000: sourcing /Users/.vim/plugged/vim-lsp/plugin/lsp.vim
+      046.000  000.500  000.500: sourcing /Users/.vim/plugged/vim-lsp/ftdetect/lsp.vim
+    ";
+
+    let cases = vec![("vim-lsp", 8.5), ("async.vim", 1.0)];
+
+    let tempdir = tempfile::tempdir()?;
+
+    let log = tempdir.path().join("vim.log");
+
+    fs::write(&log, dedent(content))?;
+
+    let data = Worker {
+      timing: TimingMode::Inclusive,
+      ..Worker::default()
+    }
+    .parse(&log, &Runtimepath::default())?;
+
+    for (key, value) in cases {
+      assert!(approx_eq!(f64, data.plugins[key], value, ulps = 2));
+    }
+
+    assert!(approx_eq!(
+      f64,
+      data.files["vim-lsp"]["autoload/lsp.vim"],
+      2.0,
+      ulps = 2
+    ));
+
+    Ok(())
+  }
+
+  #[test]
+  fn sample_require() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+
+    let root = tempdir.path().display().to_string();
+
+    for file in [
+      "telescope.nvim/plugin/telescope.lua",
+      "telescope.nvim/lua/telescope/init.lua",
+      "plenary.nvim/lua/plenary/async/init.lua",
+      "dev/plenary/lua/plenary/async/init.lua",
+    ] {
+      let path = tempdir.path().join(file);
+      fs::create_dir_all(path.parent().unwrap())?;
+      fs::write(path, "")?;
+    }
+
+    let content = format!(
+      r"
+      040.000  000.500  000.500: require('plenary.async')
+      041.000  001.500  001.000: require('telescope')
+      041.200  000.100  000.100: require('vim.lsp')
+      042.000  002.500  000.500: sourcing {root}/telescope.nvim/plugin/telescope.lua
+      "
+    );
+
+    let log = StartupLog::parse(&dedent(&content))?;
+
+    let worker = Worker {
+      editor: Command::Neovim.into(),
+      ..Worker::default()
+    };
+
+    let data = worker.sample(&log, &Runtimepath::default())?;
+
+    assert_eq!(data.plugins.len(), 2);
+    assert!(approx_eq!(
+      f64,
+      data.plugins["telescope.nvim"],
+      1.5,
+      ulps = 2
+    ));
+    assert!(approx_eq!(f64, data.plugins["plenary.nvim"], 0.5, ulps = 2));
+
+    assert_eq!(
+      data.files["plenary.nvim"].keys().collect::<Vec<_>>(),
+      vec!["lua/plenary/async/init.lua"]
+    );
+
+    let data = worker.sample(
+      &log,
+      &Runtimepath {
+        entries: vec![tempdir.path().join("plenary.nvim")],
+        vimruntime: None,
+      },
+    )?;
+
+    assert!(approx_eq!(
+      f64,
+      data.plugins["telescope.nvim"],
+      0.5,
+      ulps = 2
+    ));
+    assert!(approx_eq!(f64, data.plugins["plenary.nvim"], 0.5, ulps = 2));
+
+    let data = worker.sample(
+      &log,
+      &Runtimepath {
+        entries: vec![tempdir.path().join("dev/plenary")],
+        vimruntime: None,
+      },
+    )?;
+
+    assert!(approx_eq!(f64, data.plugins["plenary"], 0.5, ulps = 2));
+    assert_eq!(data.paths["plenary"], tempdir.path().join("dev/plenary"));
+    assert_eq!(
+      data.files["plenary"].keys().collect::<Vec<_>>(),
+      vec!["lua/plenary/async/init.lua"]
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn sample_plugin_directories() -> Result<()> {
+    let content = r"
+      038.356  000.029  000.029: sourcing /Users/.vim/plugged/vim-just/ftdetect/just.vim
+      039.955  000.028  000.028: sourcing /Users/.vim/pack/bundle/start/vim-just/ftdetect/just.vim
+      040.327  000.058  000.058: sourcing /Users/.vim/pack/bundle/start/vim-rooter/plugin/rooter.vim
+      040.530  000.048  000.048: sourcing /opt/plugins/rust.vim/after/syntax/rust.vim
+    ";
+
+    let data = Worker {
+      plugin_directories: vec![PathBuf::from("/opt/plugins/")],
+      ..Worker::default()
+    }
+    .sample(
+      &StartupLog::parse(&dedent(content))?,
+      &Runtimepath::default(),
+    )?;
+
+    let cases = vec![
+      ("vim-just (/Users/.vim/plugged)", 0.029),
+      ("vim-just (/Users/.vim/pack/bundle/start)", 0.028),
+      ("vim-rooter", 0.058),
+      ("rust.vim", 0.048),
+    ];
+
+    assert_eq!(data.plugins.len(), cases.len());
 
-    let data = Worker::new(Command::Vim, 1, false, None).parse()?;
     for (key, value) in cases {
-      assert!(approx_eq!(f64, data[key], value, ulps = 2));
+      assert!(approx_eq!(f64, data.plugins[key], value, ulps = 2));
     }
 
-    fs::remove_file("vim.log")?;
+    assert_eq!(
+      data.paths["vim-rooter"],
+      PathBuf::from("/Users/.vim/pack/bundle/start/vim-rooter")
+    );
+
+    assert_eq!(
+      data.files["rust.vim"].keys().collect::<Vec<_>>(),
+      vec!["after/syntax/rust.vim"]
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn sample_system() -> Result<()> {
+    let content = r"
+      010.000  001.000  001.000: sourcing /opt/homebrew/share/vim/vim91/syntax/syntax.vim
+      011.000  000.500  000.500: sourcing /opt/homebrew/share/vim/vim91/plugin/netrwPlugin.vim
+      012.000  000.250  000.250: sourcing /nix/store/abc-vimfiles/syntax/syntax.vim
+      013.000  000.125  000.125: sourcing /usr/share/vim/vimfiles/plugin/fzf.vim
+      014.000  002.000  002.000: sourcing /Users/.vim/plugged/vim-just/ftdetect/just.vim
+    ";
+
+    let data = Worker {
+      sys: true,
+      system_directories: vec![PathBuf::from("/nix/store/abc-vimfiles")],
+      ..Worker::default()
+    }
+    .sample(
+      &StartupLog::parse(&dedent(content))?,
+      &Runtimepath {
+        entries: Vec::new(),
+        vimruntime: Some(PathBuf::from("/opt/homebrew/share/vim/vim91")),
+      },
+    )?;
+
+    let cases = vec![
+      ("syntax/syntax.vim (/opt/homebrew/share/vim/vim91)", 1.0),
+      ("syntax/syntax.vim (/nix/store/abc-vimfiles)", 0.25),
+      ("plugin/netrwPlugin.vim", 0.5),
+      ("share/vim/vimfiles/plugin/fzf.vim", 0.125),
+      ("vim-just", 2.0),
+    ];
+
+    assert_eq!(data.plugins.len(), cases.len());
+
+    for (key, value) in cases {
+      assert!(approx_eq!(f64, data.plugins[key], value, ulps = 2));
+    }
+
+    assert_eq!(
+      data.paths["plugin/netrwPlugin.vim"],
+      PathBuf::from("/opt/homebrew/share/vim/vim91/plugin/netrwPlugin.vim")
+    );
 
     Ok(())
   }